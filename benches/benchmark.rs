@@ -2,18 +2,37 @@ use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
 use process_lib::base::*;
 use process_lib::brownian::*;
-//use process_lib::brownian_vec::*;
+use process_lib::ensemble::*;
 
-fn criterion_benchmark(c: &mut Criterion) {
-    let mut gbm = GeometricBrownianMotion::new(Point {x: 0.0, y: 0.0}, 6.0, 10.0, 1.0, 100000);
+const PATHS: usize = 1000;
+const STEPS: u32 = 1000;
 
+fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function(
-        "generate 1000",
+        "generate 100000",
         |b| b.iter( || {
+            let mut gbm = GeometricBrownianMotion::new(Point {x: 0.0, y: 100.0}, 0.05, 0.2, 0.01, 100000);
             gbm.generate();
+            black_box(gbm)
         })
     );
 }
 
-criterion_group!(benches, criterion_benchmark);
-criterion_main!(benches);
\ No newline at end of file
+fn build_gbm(rng: DefaultRng) -> GeometricBrownianMotion {
+    GeometricBrownianMotion::with_rng(Point {x: 0.0, y: 100.0}, 0.05, 0.2, 0.01, STEPS, rng)
+}
+
+// serial vs parallel generation of the same ensemble.
+fn ensemble_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ensemble 1000x1000");
+
+    group.bench_function("serial", |b| b.iter( || black_box(simulate_paths(PATHS, 42, build_gbm)) ));
+
+    #[cfg(feature = "parallel")]
+    group.bench_function("parallel", |b| b.iter( || black_box(simulate_paths_parallel(PATHS, 42, build_gbm)) ));
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark, ensemble_benchmark);
+criterion_main!(benches);
@@ -1,72 +1,174 @@
 // basic traits and types implemented by the structs.
 
+use num_traits::Float;
+use rand::{Rng, RngCore, SeedableRng};
+use rand_distr::Distribution;
+
+pub use crate::storage::{Path, PathStorage, RingBuffer, Streaming, StorageIter};
+
+// rng used by the processes unless the user supplies their own.
+// ChaCha8 output is specified by its algorithm, so the same seed yields the same
+// random stream on every platform. the models turn it into values with the portable math below:
+// same seed -> bit-identical path on every platform.
+// user-supplied distributions and closures (jump sizes, sde coefficients) are outside this guarantee.
+pub type DefaultRng = rand_chacha::ChaCha8Rng;
+
+// rng seeded from system entropy. -> non-reproducible paths.
+pub fn default_rng() -> DefaultRng {
+    DefaultRng::from_entropy()
+}
+
+// rng seeded from a fixed value. -> reproducible paths.
+pub fn seeded_rng(seed: u64) -> DefaultRng {
+    DefaultRng::seed_from_u64(seed)
+}
+
+// rng for one of many independent streams derived from the same seed.
+// used to give every path of an ensemble its own, reproducible randomness.
+pub fn stream_rng(seed: u64, stream: u64) -> DefaultRng {
+    let mut rng = DefaultRng::seed_from_u64(seed);
+    rng.set_stream(stream);
+    rng
+}
+
+// convert a constant or count into the scalar type of a process.
+// every Float can represent the small constants used by the models.
+#[inline]
+pub(crate) fn scalar<T: Float, V: num_traits::ToPrimitive>(value: V) -> T {
+    T::from(value).expect("value not representable in scalar type")
+}
+
+// rounding error of n operations on values of magnitude scale.
+// e.g. a row of n probabilities (scale 1) may miss its sum of 1 by this much.
+pub(crate) fn rounding_tolerance<T: Float>(n: usize, scale: T) -> T {
+    scalar::<T, _>(4 * n) * T::epsilon() * scale
+}
+
+// random numbers are drawn in f64 and converted, so every scalar type sees the same stream.
+
+// uniform sample in [0, 1)
+#[inline]
+pub(crate) fn uniform<T: Float, R: Rng>(rng: &mut R) -> T {
+    scalar(rng.gen::<f64>())
+}
+
+// standard normal sample N(0, 1). box-muller from two uniforms, the sine partner is discarded.
+// the processes draw all their normals here, so a test can replay the stream of a seeded process.
+#[inline]
+pub fn standard_normal<T: Float, R: Rng>(rng: &mut R) -> T {
+    // 1 - U lies in (0, 1], so ln() never sees zero.
+    let radius = (-2.0 * ln(1.0 - rng.gen::<f64>())).sqrt();
+    let angle = 2.0 * std::f64::consts::PI * rng.gen::<f64>();
+
+    scalar(radius * cos(angle))
+}
+
+// --- portable math :
+// the standard library's exp/ln/powf/sin/cos call the platform's math library, whose last bits
+// differ between platforms. libm computes them in rust: same input -> same bits everywhere.
+// sqrt and the arithmetic operators are exactly rounded by ieee 754 and need no replacement.
+// generic scalars are computed in f64 and converted, like the random numbers.
+
+#[inline]
+fn to_f64<T: Float>(value: T) -> f64 {
+    value.to_f64().expect("scalar converts to f64")
+}
+#[inline]
+pub(crate) fn exp<T: Float>(value: T) -> T {
+    scalar(libm::exp(to_f64(value)))
+}
+#[inline]
+pub(crate) fn ln<T: Float>(value: T) -> T {
+    scalar(libm::log(to_f64(value)))
+}
+#[inline]
+pub(crate) fn powf<T: Float>(base: T, exponent: T) -> T {
+    scalar(libm::pow(to_f64(base), to_f64(exponent)))
+}
+#[inline]
+pub(crate) fn sin(value: f64) -> f64 {
+    libm::sin(value)
+}
+#[inline]
+pub(crate) fn cos(value: f64) -> f64 {
+    libm::cos(value)
+}
+
+// distribution that always yields the same value. e.g. a fixed jump or step size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Constant<T>(pub T);
+
+impl<T: Copy> Distribution<T> for Constant<T> {
+    fn sample<R: Rng + ?Sized>(&self, _rng: &mut R) -> T {
+        self.0
+    }
+}
+
+// distribution given by a user closure, e.g. FromFn(|rng: &mut dyn RngCore| if rng.gen_bool(0.1) { 50.0 } else { 5.0 })
+#[derive(Debug, Clone, Copy)]
+pub struct FromFn<F>(pub F);
+
+impl<T, F: Fn(&mut dyn RngCore) -> T> Distribution<T> for FromFn<F> {
+    fn sample<R: Rng + ?Sized>(&self, mut rng: &mut R) -> T {
+        (self.0)(&mut rng)
+    }
+}
+
 pub trait Process {
+    type Scalar: Float; // numeric type of the generated points
+    type Storage: PathStorage<Self::Scalar>; // where generated elements are kept
+
     fn generate(&mut self); // calculate multiple steps
 
     fn generate_single(&mut self); // calculate a single step.
 
-    fn reset(&mut self); // remove all elements
+    fn reset(&mut self); // remove all elements and rewind, so generate() starts over
 
-    fn push_back(&mut self, boxed: Box<Node>) -> usize; // add an element to back
+    fn push_back(&mut self, point: Point<Self::Scalar>) -> usize; // add an element to back
 
-    fn pop_back(&mut self) -> Option<Box<Node>>; // remove an element from back
+    fn pop_back(&mut self) -> Option<Point<Self::Scalar>>; // remove an element from back
+
+    fn path(&self) -> &Self::Storage; // elements generated so far, as kept by the storage
 }
 
-#[derive(Debug, Clone, Copy)]
-pub struct Point {
-    pub x: f32,
-    pub y: f32,
+// f32 for games/graphics, f64 (default) for long simulations where rounding error accumulates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point<T: Float = f64> {
+    pub x: T,
+    pub y: T,
 }
 
-#[derive(Debug, Clone, Copy)]
-pub struct Node
-{
-    pub current: Point,
-    pub next: Option<*mut Node>,
-    pub prev: Option<*mut Node>,
+// struct created by calling into_iter() on the model struct.
+// yields the generated points by value, front to back.
+#[derive(Debug, Clone)]
+pub struct ProcessIter<T: Float = f64> {
+    inner: std::vec::IntoIter<Point<T>>,
 }
 
-impl Node {
-    pub fn new(current: Point) -> Self {
+impl<T: Float> From<Vec<Point<T>>> for ProcessIter<T> {
+    fn from(points: Vec<Point<T>>) -> Self {
         Self {
-            current: current,
-            next: None,
-            prev: None,
+            inner: points.into_iter(),
         }
     }
 }
 
-// struct created by calling iter() method on the model struct.
-// -> move to base.rs as a base for all model types.
-pub struct ProcessIter {
-    pub head: Option<*mut Node>,
-    pub tail: Option<*mut Node>,
-    pub len: usize,
-}
+impl<T: Float> Iterator for ProcessIter<T> {
+    type Item = Point<T>;
 
-pub trait ProcessIntoIterator {
-    type Item;
-    type ProcessIntoIter;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
 
-    fn into_iter(&mut self) -> Self::ProcessIntoIter; 
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
 }
 
-// iterator one-ended
-impl<'a> Iterator for ProcessIter {
-    type Item = Point;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        match self.head {
-            None => {
-                None
-            },
-            Some(head) => {
-                unsafe {
-                    self.head = (*head).next;
-                    // return copy of current element, not next element.
-                    Some((*head).current)
-                }
-            }
-        }
+impl<T: Float> DoubleEndedIterator for ProcessIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
     }
-}
\ No newline at end of file
+}
+
+impl<T: Float> ExactSizeIterator for ProcessIter<T> {}
@@ -1,148 +1,229 @@
+// --- theory :
+// sequence of independent trials, each a success with probability p.
+// raw view: outcome of every trial.   counting view: number of successes so far (binomial process).
+
+// --- here:
+// the path holds the raw trials: y = initial.y + step.y on success, initial.y otherwise.
+// the outcome of every trial is kept alongside in the same storage type (y = 1 success, 0 failure),
+// so a bounded backend bounds it too. trial views cover the trials the storage still holds.
+
+use num_traits::Float;
 use rand::Rng;
 
 use crate::base::*;
 
-#[derive(Debug)]
+// lengths of uninterrupted runs of equal outcomes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RunStats {
+    pub runs: usize, // number of runs of either outcome
+    pub success_runs: usize, // number of runs of successes
+    pub longest_success: usize, // longest run of successes
+    pub longest_failure: usize, // longest run of failures
+}
+
+#[derive(Debug, Clone)]
+pub struct Bernoulli<T: Float = f64, R: Rng = DefaultRng, S: PathStorage<T> = Path<T>> {
+    path: S, // generated points
 
-pub struct Bernoulli {
-    head: Option<*mut Node>,
-    tail: Option<*mut Node>,
+    initial: Point<T>,
 
-    len: usize,
+    step: Point<T>, // x- and y-difference between outcome 0 and 1
+    probability: T, // p: probability of a success
+    remaining: u32, // steps left to generate. counted, so float rounding cannot add a step.
+    loops: u32, // steps per generation. reset restores remaining to it.
 
-    initial: Point,
+    outcomes: S, // outcome of every trial: y = 1 success, y = 0 failure, same x as the path
 
-    step: Point, // x- and y-difference between outcome 0 and 1
-    distance: f32, // total distance: multiple of step.x
+    rng: R, // source of randomness. seeded -> reproducible path.
 }
 
-impl IntoIterator for Bernoulli {
-    type Item = Point;
-    type IntoIter = ProcessIter;
+impl<T: Float, R: Rng, S: PathStorage<T>> IntoIterator for Bernoulli<T, R, S> {
+    type Item = Point<T>;
+    type IntoIter = ProcessIter<T>;
 
     fn into_iter(self) -> Self::IntoIter {
-        ProcessIter {
-            head: self.head,
-            tail: self.tail,
-            len: (self.distance / self.step.x) as usize,
-        }
+        self.path.into_iter()
     }
 }
 
-impl Process for Bernoulli {
+impl<T: Float, R: Rng, S: PathStorage<T>> Process for Bernoulli<T, R, S> {
+    type Scalar = T;
+    type Storage = S;
+
+    // a manual point counts as the outcome its y lies closer to. halfway, or with step.y = 0: failure.
     #[inline]
-    fn push_back(&mut self, mut boxed: Box<Node>) -> usize {
-
-        boxed.next = None;
-        boxed.prev = self.tail; // assign current tail node of container to prev pointer of new node. 
-                                // -> (the container's last element)
-        unsafe {
-            let node = Box::leak(boxed); // leak the box.
-
-            match self.tail {
-                // no elements in container. create head node.
-                None => self.head = Some(node),
-                // elements exist. assign next pointer of tail to new node.
-                Some(tail) => (*tail).next = Some(node),
-            }
+    fn push_back(&mut self, point: Point<T>) -> usize {
+        let success = (point.y - (self.initial.y + self.step.y)).abs() < (point.y - self.initial.y).abs();
 
-            // independently of whether tail exists, assign current node to tail.
-            self.tail = Some(node);
-        }
-        self.len += 1;
-        self.len
+        self.record(point, success)
     }
     #[inline]
-    fn pop_back(&mut self) -> Option<Box<Node>> {
-        let result = match self.tail {
-            None => {
-                // hint: not necessary to Box::from_raw(self.head), since the memory is automatically boxed when
-                // the first self.tail is popped (below)
-                self.head = None;
-                None
-            }
-            Some(tail) => {
-                unsafe {
-                    let mut boxed = Box::from_raw(tail);
-                    boxed.next = None;
-                    if let Some(prev) = boxed.prev {
-                        self.tail = Some(prev);
-                    }
-                    Some(boxed)
-                }
-            }
-        };
-
-        result
+    fn pop_back(&mut self) -> Option<Point<T>> {
+        self.outcomes.pop_back();
+        self.path.pop_back()
+    }
+    fn path(&self) -> &S {
+        &self.path
     }
     fn reset(&mut self) {
-        // if stopped, either end reached (reset complete), or linked list is broken.
-        unsafe {
-            // tail: currently the last node.
-            while let Some(tail) = self.tail {
-
-                // box the value. making it memory safe.
-                let boxed = Box::from_raw(tail);
-
-                boxed.prev.map( |node| {
-                    (*node).next = None;
-                });
-                self.tail = boxed.prev;
-            }
-            self.head = None;
-        }
+        self.path.clear();
+        self.outcomes.clear();
+
+        self.remaining = self.loops;
     }
     fn generate(&mut self) {
-        while self.distance > 0.0 {
+        while self.remaining > 0 {
             self.generate_single();
         }
     }
     fn generate_single(&mut self) {
-        let mut point: Point = Point {x: 0.0, y: 0.0 };
+        let mut point: Point<T> = Point {x: T::zero(), y: T::zero() };
 
-        if let Some(tail) = self.tail {
-            unsafe {
-                let cur = &(*tail).current;
-                point.x = cur.x + self.step.x;
-            }
-            if rand::thread_rng().gen_ratio(1, 2) {
-                // upper 50% : move up 
-                point.y = self.initial.y + self.step.y;
-            } else {
-                // lower 50% : stay
-                point.y = self.initial.y;
-            }
-        } else {
-            point.x = self.initial.x;
+        point.x = match self.path.tail() {
+            Some(tail) => tail.x + self.step.x,
+            None => self.initial.x,
+        };
 
-            if rand::thread_rng().gen_ratio(1, 2) {
-                // upper 50% : move up 
-                point.y = self.initial.y + self.step.y;
-            } else {
-                // lower 50% : stay
-                point.y = self.initial.y;
-            }
+        // outcome is recorded as drawn, not recovered from y.
+        let success = uniform::<T, R>(&mut self.rng) < self.probability;
+
+        if success {
+            // success : move up
+            point.y = self.initial.y + self.step.y;
+        } else {
+            // failure : stay
+            point.y = self.initial.y;
         }
 
-        let boxed = Box::new( Node::new(point) );
-        self.push_back(boxed);
+        self.record(point, success);
 
-        self.distance -= self.step.x;
+        self.remaining = self.remaining.saturating_sub(1);
     }
 }
 
-impl Bernoulli {
-    pub fn new(initial: Point, step: Point, steps: u32) -> Self {
-        Self {
-            head: None,
-            tail: None,
+impl<T: Float> Bernoulli<T> {
+    pub fn new(initial: Point<T>, step: Point<T>, steps: u32) -> Self {
+        Self::with_rng(initial, step, steps, default_rng())
+    }
+    // same seed and parameters -> identical path.
+    pub fn with_seed(initial: Point<T>, step: Point<T>, steps: u32, seed: u64) -> Self {
+        Self::with_rng(initial, step, steps, seeded_rng(seed))
+    }
+}
 
-            len: 0,
+impl<T: Float, R: Rng> Bernoulli<T, R> {
+    // fair trials (p = 0.5). change with with_probability.
+    pub fn with_rng(initial: Point<T>, step: Point<T>, steps: u32, rng: R) -> Self {
+        Self {
+            path: Path::with_capacity(steps as usize),
 
             // model parameters
-            initial: initial,
-            step: step,
-            distance: steps as f32 * step.x,
+            initial,
+            step,
+            probability: scalar(0.5),
+            remaining: steps,
+            loops: steps,
+
+            outcomes: Path::with_capacity(steps as usize),
+
+            rng,
+        }
+    }
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> Bernoulli<T, R, S> {
+    // swap the storage backend. points generated so far are dropped, generation starts over from the initial point.
+    pub fn with_storage<U: PathStorage<T> + Clone>(self, storage: U) -> Bernoulli<T, R, U> {
+        Bernoulli {
+            path: storage.clone(),
+            outcomes: storage,
+
+            initial: self.initial,
+            step: self.step,
+            probability: self.probability,
+            remaining: self.loops,
+            loops: self.loops,
+            rng: self.rng,
         }
     }
-}
\ No newline at end of file
+    pub fn with_probability(mut self, probability: T) -> Self {
+        self.set_probability(probability);
+        self
+    }
+    pub fn set_probability(&mut self, probability: T) {
+        assert!(probability >= T::zero() && probability <= T::one(), "probability must lie in [0, 1]");
+
+        self.probability = probability;
+    }
+    pub fn probability(&self) -> T {
+        self.probability
+    }
+    // store a trial and its outcome.
+    fn record(&mut self, point: Point<T>, success: bool) -> usize {
+        let outcome = if success { T::one() } else { T::zero() };
+
+        self.outcomes.push_back( Point { x: point.x, y: outcome } );
+        self.path.push_back(point)
+    }
+    // outcome of every trial kept by the storage, true: success.
+    pub fn trials(&self) -> impl Iterator<Item = bool> + '_ {
+        self.outcomes.iter().map(|outcome| outcome.y > T::zero())
+    }
+    // outcome storage: y = 1 success, y = 0 failure.
+    pub fn outcomes(&self) -> &S {
+        &self.outcomes
+    }
+    // number of successes kept by the storage
+    pub fn successes(&self) -> usize {
+        self.trials().filter(|&success| success).count()
+    }
+    // binomial counting process: y = initial.y + step.y * (successes up to and including this trial)
+    pub fn counting_process(&self) -> Vec<Point<T>> {
+        let mut count = self.initial.y;
+
+        self.outcomes
+            .iter()
+            .map(|outcome| {
+                if outcome.y > T::zero() {
+                    count = count + self.step.y;
+                }
+                Point { x: outcome.x, y: count }
+            })
+            .collect()
+    }
+    // x of the k-th success (k = 1: first success). None if there were fewer than k successes.
+    pub fn time_of_success(&self, k: usize) -> Option<T> {
+        if k == 0 {
+            return None;
+        }
+        self.outcomes
+            .iter()
+            .filter(|outcome| outcome.y > T::zero())
+            .nth(k - 1)
+            .map(|outcome| outcome.x)
+    }
+    pub fn run_stats(&self) -> RunStats {
+        let mut stats = RunStats::default();
+        let mut previous = None;
+        let mut length = 0;
+
+        for success in self.trials() {
+            if previous == Some(success) {
+                length += 1;
+            } else {
+                length = 1;
+                stats.runs += 1;
+                if success {
+                    stats.success_runs += 1;
+                }
+            }
+            if success {
+                stats.longest_success = stats.longest_success.max(length);
+            } else {
+                stats.longest_failure = stats.longest_failure.max(length);
+            }
+            previous = Some(success);
+        }
+        stats
+    }
+}
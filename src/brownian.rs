@@ -1,169 +1,608 @@
+use num_traits::Float;
 use rand::Rng;
 
 use crate::base::*;
 
+// exact solution of the gbm sde over a single step:
+// S(t + dt) = S(t) * exp( (mu - sigma^2 / 2) * dt + sigma * sqrt(dt) * Z ),  Z ~ N(0, 1)
+// -> log-returns are normal with mean (mu - sigma^2 / 2) * dt and variance sigma^2 * dt.
+pub(crate) fn gbm_step<T: Float, R: Rng>(rng: &mut R, value: T, drift: T, volatility: T, step: T) -> T {
+    let z: T = standard_normal(rng);
+    let half: T = scalar(0.5);
+
+    value * exp( (drift - half * volatility * volatility) * step + volatility * step.sqrt() * z )
+}
+
 #[derive(Debug, Clone)]
-pub struct GeometricBrownianMotion
+pub struct GeometricBrownianMotion<T: Float = f64, R: Rng = DefaultRng, S: PathStorage<T> = Path<T>>
 {
-    head: Option<*mut Node>,
-    tail: Option<*mut Node>,
+    path: S, // generated points
 
-    len: usize,
+    initial: Point<T>, // starting point
 
-    initial: Point, // starting point
+    drift: T, // drift rate (mu): expected relative change per unit of x.
+    volatility: T, // volatility (sigma): standard deviation of log-returns per unit of x. constant over the path,
+                    // see heston for a variance that fluctuates over time.
 
-    drift: f32, // stochastic drift: max. change from the average value in the stochastic process.
-    volatility: f32, // stochastic volatility
-                    // assumption: volatility in prices/etc is not constant over time.
-                    // purpose: value allows volatility in the underlying object to fluctuate over time.
+    step: T, // distance per loop/step.
+    remaining: u32, // steps left to generate. counted, so float rounding cannot add a step.
+    loops: u32, // steps per generation. reset restores remaining to it.
 
-    step: f32, // distance per loop/step.
-    distance: f32, // total distance: multiple of step
+    rng: R, // source of randomness. seeded -> reproducible path.
 }
 
-impl IntoIterator for GeometricBrownianMotion {
-    type Item = Point;
-    type IntoIter = ProcessIter;
+impl<T: Float, R: Rng, S: PathStorage<T>> IntoIterator for GeometricBrownianMotion<T, R, S> {
+    type Item = Point<T>;
+    type IntoIter = ProcessIter<T>;
 
     fn into_iter(self) -> Self::IntoIter {
-        ProcessIter {
-            head: self.head,
-            tail: self.tail,
-            len: (self.distance / self.step) as usize,
-        }
+        self.path.into_iter()
     }
 }
 
-impl Process for GeometricBrownianMotion {
+impl<T: Float, R: Rng, S: PathStorage<T>> Process for GeometricBrownianMotion<T, R, S> {
+    type Scalar = T;
+    type Storage = S;
+
     #[inline]
-    fn push_back(&mut self, mut boxed: Box<Node>) -> usize {
-
-        boxed.next = None;
-        boxed.prev = self.tail; // assign current tail node of container to prev pointer of new node. 
-                                // -> (the container's last element)
-        unsafe {
-            let node: *mut Node = Box::leak(boxed);
-
-            match self.tail {
-                // no elements in container. create head node.
-                None => self.head = Some(node),
-                // elements exist. assign next pointer of tail to new node.
-                Some(tail) => (*tail).next = Some(node),
-            }
+    fn push_back(&mut self, point: Point<T>) -> usize {
+        self.path.push_back(point)
+    }
+    #[inline]
+    fn pop_back(&mut self) -> Option<Point<T>> {
+        self.path.pop_back()
+    }
+    fn path(&self) -> &S {
+        &self.path
+    }
+
+    // generate motion from given attributes
+    fn generate(&mut self) {  
+        while self.remaining > 0 {
+            self.generate_single();
+        }
+    }
+    // generate single value
+    fn generate_single(&mut self) {
+        let cur = *self.path.tail().unwrap_or(&self.initial);
+
+        let res = gbm_step(&mut self.rng, cur.y, self.drift, self.volatility, self.step);
+
+        self.push_back( Point { x: cur.x + self.step, y: res } );
+
+        self.remaining = self.remaining.saturating_sub(1);
+    }
+    fn reset(&mut self) {
+        self.path.clear();
+
+        self.remaining = self.loops;
+    }
+}
+
+impl<T: Float> GeometricBrownianMotion<T> {
+    pub fn new(initial: Point<T>, drift: T, volatility: T, step: T, loops: u32) -> Self {
+        Self::with_rng(initial, drift, volatility, step, loops, default_rng())
+    }
+    // same seed and parameters -> identical path.
+    pub fn with_seed(initial: Point<T>, drift: T, volatility: T, step: T, loops: u32, seed: u64) -> Self {
+        Self::with_rng(initial, drift, volatility, step, loops, seeded_rng(seed))
+    }
+}
+
+impl<T: Float, R: Rng> GeometricBrownianMotion<T, R> {
+    pub fn with_rng(initial: Point<T>, drift: T, volatility: T, step: T, loops: u32, rng: R) -> Self {
+        Self {
+            path: Path::with_capacity(loops as usize),
+    
+            // model parameters
+            initial,
+            drift,
+            volatility,
+            step,
+            remaining: loops,
+            loops,
 
-            // independently of whether tail exists, assign current node to tail.
-            self.tail = Some(node);
+            rng,
         }
-        self.len += 1;
-        self.len
     }
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> GeometricBrownianMotion<T, R, S> {
+    // swap the storage backend. points generated so far are dropped, generation starts over from the initial point.
+    pub fn with_storage<U: PathStorage<T>>(self, storage: U) -> GeometricBrownianMotion<T, R, U> {
+        GeometricBrownianMotion {
+            path: storage,
+
+            initial: self.initial,
+            drift: self.drift,
+            volatility: self.volatility,
+            step: self.step,
+            remaining: self.loops,
+            loops: self.loops,
+            rng: self.rng,
+        }
+    }
+    pub fn set_drift(&mut self, drift: T) {
+        self.drift = drift;
+    }
+    pub fn set_volatility(&mut self, volatility: T) {
+        self.volatility = volatility
+    }
+    pub fn set_step(&mut self, step: T) {
+        self.step = step;
+    }
+}
+
+// wiener process: W(t + dt) = W(t) + mu * dt + sigma * sqrt(dt) * Z,  Z ~ N(0, 1)
+// -> with drift None and scale 1.0 the increments have mean 0 and variance dt.
+#[derive(Debug, Clone)]
+pub struct StandardBrownianMotion<T: Float = f64, R: Rng = DefaultRng, S: PathStorage<T> = Path<T>>
+{
+    path: S, // generated points
+
+    initial: Point<T>, // starting point
+
+    drift: Option<T>, // with or without drift parameter determined at initialization.
+    scale: T, // sigma: scales the gaussian increments.
+
+    step: T, // distance per loop/step.
+    remaining: u32, // steps left to generate. counted, so float rounding cannot add a step.
+    loops: u32, // steps per generation. reset restores remaining to it.
+
+    rng: R, // source of randomness. seeded -> reproducible path.
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> IntoIterator for StandardBrownianMotion<T, R, S> {
+    type Item = Point<T>;
+    type IntoIter = ProcessIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.path.into_iter()
+    }
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> Process for StandardBrownianMotion<T, R, S> {
+    type Scalar = T;
+    type Storage = S;
+
     #[inline]
-    fn pop_back(&mut self) -> Option<Box<Node>> {
-        let result = match self.tail {
-            None => {
-                // hint: not necessary to Box::from_raw(self.head), since the memory is automatically boxed when
-                // the first self.tail is popped (below)
-                self.head = None;
-                None
-            }
-            Some(tail) => {
-                unsafe {
-                    let mut boxed = Box::from_raw(tail);
-                    boxed.next = None;
-                    if let Some(prev) = boxed.prev {
-                        self.tail = Some(prev);
-                    }
-                    Some(boxed)
-                }
-            }
-        };
+    fn push_back(&mut self, point: Point<T>) -> usize {
+        self.path.push_back(point)
+    }
+    #[inline]
+    fn pop_back(&mut self) -> Option<Point<T>> {
+        self.path.pop_back()
+    }
+    fn path(&self) -> &S {
+        &self.path
+    }
 
-        result
+    // generate motion from given attributes
+    fn generate(&mut self) {
+        while self.remaining > 0 {
+            self.generate_single();
+        }
+    }
+    // generate single value
+    fn generate_single(&mut self) {
+        let cur = *self.path.tail().unwrap_or(&self.initial);
+
+        let z: T = standard_normal(&mut self.rng);
+        let increment = self.drift.unwrap_or_else(T::zero) * self.step + self.scale * self.step.sqrt() * z;
+
+        self.push_back( Point { x: cur.x + self.step, y: cur.y + increment } );
+
+        self.remaining = self.remaining.saturating_sub(1);
+    }
+    fn reset(&mut self) {
+        self.path.clear();
+
+        self.remaining = self.loops;
+    }
+}
+
+impl<T: Float> StandardBrownianMotion<T> {
+    pub fn new(initial: Point<T>, drift: Option<T>, scale: T, step: T, loops: u32) -> Self {
+        Self::with_rng(initial, drift, scale, step, loops, default_rng())
+    }
+    // same seed and parameters -> identical path.
+    pub fn with_seed(initial: Point<T>, drift: Option<T>, scale: T, step: T, loops: u32, seed: u64) -> Self {
+        Self::with_rng(initial, drift, scale, step, loops, seeded_rng(seed))
+    }
+}
+
+impl<T: Float, R: Rng> StandardBrownianMotion<T, R> {
+    pub fn with_rng(initial: Point<T>, drift: Option<T>, scale: T, step: T, loops: u32, rng: R) -> Self {
+        Self {
+            path: Path::with_capacity(loops as usize),
+
+            // model parameters
+            initial,
+            drift,
+            scale,
+            step,
+            remaining: loops,
+            loops,
+
+            rng,
+        }
+    }
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> StandardBrownianMotion<T, R, S> {
+    // swap the storage backend. points generated so far are dropped, generation starts over from the initial point.
+    pub fn with_storage<U: PathStorage<T>>(self, storage: U) -> StandardBrownianMotion<T, R, U> {
+        StandardBrownianMotion {
+            path: storage,
+
+            initial: self.initial,
+            drift: self.drift,
+            scale: self.scale,
+            step: self.step,
+            remaining: self.loops,
+            loops: self.loops,
+            rng: self.rng,
+        }
+    }
+    pub fn set_drift(&mut self, drift: Option<T>) {
+        self.drift = drift;
+    }
+    pub fn set_scale(&mut self, scale: T) {
+        self.scale = scale;
+    }
+    pub fn set_step(&mut self, step: T) {
+        self.step = step;
+    }
+}
+
+// value of a brownian motion at x, conditioned on passing through a and b (a.x <= x <= b.x):
+// N( a.y + (b.y - a.y) * (x - a.x) / (b.x - a.x),  sigma^2 * (x - a.x) * (b.x - x) / (b.x - a.x) )
+pub(crate) fn bridge_sample<T: Float, R: Rng>(rng: &mut R, a: Point<T>, b: Point<T>, x: T, sigma: T) -> T {
+    let span = b.x - a.x;
+    if span <= T::zero() {
+        return b.y;
+    }
+    let z: T = standard_normal(rng);
+
+    let mean = a.y + (b.y - a.y) * (x - a.x) / span;
+    let variance = sigma * sigma * (x - a.x) * (b.x - x) / span;
+
+    mean + variance.max(T::zero()).sqrt() * z
+}
+
+// refine a coarse path: every level inserts a conditioned midpoint between each pair of neighbours,
+// so the result has (n - 1) * 2^levels + 1 points and still passes through every original point.
+// works with the points of any process, e.g. bridge_refine(process.path().iter().copied(), sigma, 3, &mut rng).
+pub fn bridge_refine<T: Float, R: Rng, I: IntoIterator<Item = Point<T>>>(points: I, sigma: T, levels: u32, rng: &mut R) -> Vec<Point<T>> {
+    let mut points: Vec<Point<T>> = points.into_iter().collect();
+    let half: T = scalar(0.5);
+
+    for _ in 0..levels {
+        let mut refined = Vec::with_capacity(points.len() * 2);
+
+        for pair in points.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            let x = (a.x + b.x) * half;
+
+            refined.push(a);
+            refined.push( Point { x, y: bridge_sample(rng, a, b, x, sigma) } );
+        }
+        if let Some(&last) = points.last() {
+            refined.push(last);
+        }
+        points = refined;
+    }
+    points
+}
+
+// brownian motion pinned at both ends: starts at start and is forced to arrive at end.
+// each step is drawn conditioned on the end point, the last step lands exactly on end.
+#[derive(Debug, Clone)]
+pub struct BrownianBridge<T: Float = f64, R: Rng = DefaultRng, S: PathStorage<T> = Path<T>>
+{
+    path: S, // generated points
+
+    initial: Point<T>, // starting point
+    end: Point<T>, // point the path must arrive at
+
+    scale: T, // sigma: scales the gaussian increments.
+
+    step: T, // distance per loop/step.
+    remaining: u32, // steps left to generate. counted, so float rounding cannot add a step.
+    loops: u32, // steps per generation. reset restores remaining to it.
+
+    rng: R, // source of randomness. seeded -> reproducible path.
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> IntoIterator for BrownianBridge<T, R, S> {
+    type Item = Point<T>;
+    type IntoIter = ProcessIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.path.into_iter()
+    }
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> Process for BrownianBridge<T, R, S> {
+    type Scalar = T;
+    type Storage = S;
+
+    #[inline]
+    fn push_back(&mut self, point: Point<T>) -> usize {
+        self.path.push_back(point)
+    }
+    #[inline]
+    fn pop_back(&mut self) -> Option<Point<T>> {
+        self.path.pop_back()
+    }
+    fn path(&self) -> &S {
+        &self.path
     }
 
     // generate motion from given attributes
-    fn generate(&mut self) {  
-        while self.distance > 0.0 {
+    fn generate(&mut self) {
+        while self.remaining > 0 {
             self.generate_single();
         }
     }
     // generate single value
     fn generate_single(&mut self) {
-        if let Some(tail) = self.tail {
-            unsafe {
-                let cur = &(*tail).current;
-
-                let res= cur.y * self.step * (self.drift as f32) +
-                    self.volatility * ( rand::thread_rng().gen_range(0..(self.step.sqrt() as u32)) as f32 );
-                
-                let boxed = Box::new( Node::new(
-                    Point {
-                                x: cur.x + self.step, y: res
-                            })
-                );
-
-                self.push_back(boxed);
-            }
+        if self.remaining == 0 {
+            return;
+        }
+        let cur = *self.path.tail().unwrap_or(&self.initial);
+
+        // last step: land on the end point instead of accumulating rounding in x.
+        let next = if self.remaining == 1 {
+            self.end
         } else {
-            let res = self.initial.y * self.step * self.drift +
-                self.volatility * ( rand::thread_rng().gen_range(0..(self.step.sqrt() as u32)) as f32 );
+            let x = cur.x + self.step;
+            Point { x, y: bridge_sample(&mut self.rng, cur, self.end, x, self.scale) }
+        };
 
-            let boxed = Box::new( Node::new(
-                Point {
-                            x: self.initial.x + self.step, y: res
-                        })
-            );
-            self.push_back(boxed);
-        }
+        self.push_back(next);
 
-        self.distance -= self.step;
+        self.remaining = self.remaining.saturating_sub(1);
     }
     fn reset(&mut self) {
-        // if stopped, either end reached (reset complete), or linked list is broken.
-        unsafe {
-            // tail: currently the last node.
-            while let Some(tail) = self.tail {
-
-                // box the value. making it memory safe.
-                let boxed = Box::from_raw(tail);
-
-                boxed.prev.map( |node| {
-                    (*node).next = None;
-                });
-                self.tail = boxed.prev;
+        self.path.clear();
+
+        self.remaining = self.loops;
+    }
+}
+
+impl<T: Float> BrownianBridge<T> {
+    pub fn new(initial: Point<T>, end: Point<T>, scale: T, loops: u32) -> Self {
+        Self::with_rng(initial, end, scale, loops, default_rng())
+    }
+    // same seed and parameters -> identical path.
+    pub fn with_seed(initial: Point<T>, end: Point<T>, scale: T, loops: u32, seed: u64) -> Self {
+        Self::with_rng(initial, end, scale, loops, seeded_rng(seed))
+    }
+}
+
+impl<T: Float, R: Rng> BrownianBridge<T, R> {
+    // loops steps of equal length from initial.x to end.x.
+    pub fn with_rng(initial: Point<T>, end: Point<T>, scale: T, loops: u32, rng: R) -> Self {
+        assert!(end.x > initial.x, "end point must lie after the starting point");
+        assert!(loops > 0, "a bridge needs at least one step");
+
+        Self {
+            path: Path::with_capacity(loops as usize),
+
+            // model parameters
+            initial,
+            end,
+            scale,
+            step: (end.x - initial.x) / scalar(loops),
+            remaining: loops,
+            loops,
+
+            rng,
+        }
+    }
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> BrownianBridge<T, R, S> {
+    // swap the storage backend. points generated so far are dropped, generation starts over from the initial point.
+    pub fn with_storage<U: PathStorage<T>>(self, storage: U) -> BrownianBridge<T, R, U> {
+        BrownianBridge {
+            path: storage,
+
+            initial: self.initial,
+            end: self.end,
+            scale: self.scale,
+            step: self.step,
+            remaining: self.loops,
+            loops: self.loops,
+            rng: self.rng,
+        }
+    }
+    pub fn set_scale(&mut self, scale: T) {
+        self.scale = scale;
+    }
+}
+
+// lower triangular L with L * L^T = matrix. accepts positive semi-definite matrices:
+// a column whose pivot vanishes (perfectly dependent variable) is left at zero.
+// panics if the matrix is not positive semi-definite.
+pub fn cholesky<T: Float>(matrix: &[Vec<T>]) -> Vec<Vec<T>> {
+    let n = matrix.len();
+    let scale = (0..n).fold(T::zero(), |max, i| max.max(matrix[i][i].abs()));
+    let eps = rounding_tolerance(n, scale);
+    let mut lower = vec![vec![T::zero(); n]; n];
+
+    for j in 0..n {
+        let pivot = (0..j).fold(matrix[j][j], |sum, k| sum - lower[j][k] * lower[j][k]);
+
+        assert!(pivot >= -eps, "matrix is not positive semi-definite");
+        if pivot <= eps {
+            // dependent column: the remaining entries must already be explained by earlier columns.
+            for i in j + 1..n {
+                let residual = (0..j).fold(matrix[i][j], |sum, k| sum - lower[i][k] * lower[j][k]);
+                assert!(residual.abs() <= eps, "matrix is not positive semi-definite");
             }
-            self.head = None;
+            continue;
+        }
+        let diagonal = pivot.sqrt();
+        lower[j][j] = diagonal;
+
+        for i in j + 1..n {
+            let residual = (0..j).fold(matrix[i][j], |sum, k| sum - lower[i][k] * lower[j][k]);
+            lower[i][j] = residual / diagonal;
         }
     }
+    lower
+}
+
+// per-asset parameters of a correlated basket.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiGbmParams<T: Float = f64> {
+    pub drift: Vec<T>, // mu of every asset
+    pub volatility: Vec<T>, // sigma of every asset
+    pub correlation: Vec<Vec<T>>, // correlation of the brownian drivers: symmetric, unit diagonal, positive semi-definite
 }
 
-impl GeometricBrownianMotion {
-    pub fn new(initial: Point, drift: f32, volatility: f32, step: f32, loops: u32) -> Self {
+// prices of every asset at the same x.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiPoint<T: Float = f64> {
+    pub x: T,
+    pub y: Vec<T>,
+}
+
+// --- theory :
+// every asset follows its own gbm, the brownian drivers are correlated:
+// S_i(t + dt) = S_i(t) * exp( (mu_i - sigma_i^2 / 2) * dt + sigma_i * sqrt(dt) * Z_i ),  corr(Z_i, Z_j) = rho_ij
+
+// --- here:
+// Z = L * E with independent standard normals E and the cholesky factor L of the correlation matrix.
+// points hold a price per asset, so this process keeps its own point list instead of a point path,
+// with the same generate/reset interface. asset_path extracts a single asset as a path.
+#[derive(Debug, Clone)]
+pub struct MultiGeometricBrownianMotion<T: Float = f64, R: Rng = DefaultRng> {
+    points: Vec<MultiPoint<T>>, // generated points
+
+    initial: MultiPoint<T>, // starting point. initial.y: starting price of every asset
+
+    params: MultiGbmParams<T>,
+    factor: Vec<Vec<T>>, // cholesky factor of the correlation matrix
+
+    step: T, // distance per loop/step.
+    remaining: u32, // steps left to generate. counted, so float rounding cannot add a step.
+    loops: u32, // steps per generation. reset restores remaining to it.
+
+    rng: R, // source of randomness. seeded -> reproducible path.
+}
+
+impl<T: Float> MultiGeometricBrownianMotion<T> {
+    pub fn new(initial: MultiPoint<T>, params: MultiGbmParams<T>, step: T, loops: u32) -> Self {
+        Self::with_rng(initial, params, step, loops, default_rng())
+    }
+    // same seed and parameters -> identical path.
+    pub fn with_seed(initial: MultiPoint<T>, params: MultiGbmParams<T>, step: T, loops: u32, seed: u64) -> Self {
+        Self::with_rng(initial, params, step, loops, seeded_rng(seed))
+    }
+}
+
+impl<T: Float, R: Rng> MultiGeometricBrownianMotion<T, R> {
+    // panics if the sizes disagree or the correlation matrix is not a valid correlation matrix.
+    pub fn with_rng(initial: MultiPoint<T>, params: MultiGbmParams<T>, step: T, loops: u32, rng: R) -> Self {
+        let factor = Self::validate(&initial, &params);
+
         Self {
-            head: None,
-            tail: None,
-    
-            len: 0,
-    
+            points: Vec::with_capacity(loops as usize),
+
             // model parameters
-            initial: initial,
-            drift: drift,
-            volatility: volatility,
-            step: step,
-            distance: loops as f32 * step,
+            initial,
+            params,
+            factor,
+            step,
+            remaining: loops,
+            loops,
+
+            rng,
         }
     }
-    pub fn set_drift(&mut self, drift: f32) {
-        self.drift = drift;
+    // generate motion from given attributes
+    pub fn generate(&mut self) {
+        while self.remaining > 0 {
+            self.generate_single();
+        }
     }
-    pub fn set_volatility(&mut self, volatility: f32) {
-        self.volatility = volatility
+    // generate a single step for every asset
+    pub fn generate_single(&mut self) {
+        let rng = &mut self.rng;
+        let independent: Vec<T> = (0..self.factor.len()).map(|_| standard_normal(rng)).collect();
+
+        let cur = self.points.last().unwrap_or(&self.initial);
+        let x = cur.x + self.step;
+        let half: T = scalar(0.5);
+        let root_step = self.step.sqrt();
+
+        let y = cur.y
+            .iter()
+            .enumerate()
+            .map(|(i, &price)| {
+                let z = self.factor[i].iter().zip(&independent).fold(T::zero(), |sum, (&l, &e)| sum + l * e);
+                let (drift, volatility) = (self.params.drift[i], self.params.volatility[i]);
+
+                price * exp( (drift - half * volatility * volatility) * self.step + volatility * root_step * z )
+            })
+            .collect();
+
+        self.points.push( MultiPoint { x, y } );
+
+        self.remaining = self.remaining.saturating_sub(1);
     }
-    pub fn set_step(&mut self, step: f32) {
+    // remove all points
+    pub fn reset(&mut self) {
+        self.points.clear();
+
+        self.remaining = self.loops;
+    }
+    pub fn pop_back(&mut self) -> Option<MultiPoint<T>> {
+        self.points.pop()
+    }
+    // generated points, in order of generation.
+    pub fn points(&self) -> &[MultiPoint<T>] {
+        &self.points
+    }
+    // path of a single asset
+    pub fn asset_path(&self, asset: usize) -> Path<T> {
+        let mut path = Path::with_capacity(self.points.len());
+        for point in &self.points {
+            path.push_back( Point { x: point.x, y: point.y[asset] } );
+        }
+        path
+    }
+    pub fn assets(&self) -> usize {
+        self.factor.len()
+    }
+    pub fn params(&self) -> &MultiGbmParams<T> {
+        &self.params
+    }
+    // panics unless params match the number of assets and form a valid correlation matrix.
+    pub fn set_params(&mut self, params: MultiGbmParams<T>) {
+        self.factor = Self::validate(&self.initial, &params);
+        self.params = params;
+    }
+    pub fn set_step(&mut self, step: T) {
         self.step = step;
     }
-}
+    // checks sizes and correlation matrix, returns its cholesky factor.
+    fn validate(initial: &MultiPoint<T>, params: &MultiGbmParams<T>) -> Vec<Vec<T>> {
+        let n = initial.y.len();
+        let eps = rounding_tolerance(n, T::one());
 
-struct StandardBrownianMotion {
-    drift: Option<f32>, // with or without drift parameter determined at initialization.
-}
\ No newline at end of file
+        assert!(n > 0, "a basket needs at least one asset");
+        assert!(params.drift.len() == n && params.volatility.len() == n, "every asset needs a drift and a volatility");
+        assert!(params.correlation.len() == n && params.correlation.iter().all(|row| row.len() == n), "correlation matrix must be n x n");
+        assert!((0..n).all(|i| (params.correlation[i][i] - T::one()).abs() <= eps), "correlation matrix must have a unit diagonal");
+        assert!(
+            (0..n).all(|i| (0..n).all(|j| (params.correlation[i][j] - params.correlation[j][i]).abs() <= eps && params.correlation[i][j].abs() <= T::one())),
+            "correlation matrix must be symmetric with entries in [-1, 1]"
+        );
+
+        cholesky(&params.correlation)
+    }
+}
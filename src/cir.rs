@@ -0,0 +1,313 @@
+// --- theory :
+// cox-ingersoll-ross short-rate model. mean-reverting like ornstein-uhlenbeck, but the noise
+// shrinks with the square root of the value, so the rate cannot become negative.
+// dX = kappa * (theta - X) dt + sigma * sqrt(X) dW
+
+// feller condition: 2 * kappa * theta >= sigma^2  ->  zero is never reached.
+// if violated, zero is reachable (but the process is reflected there, never negative).
+
+// --- here:
+// exact scheme: given X(t), X(t + dt) / c follows a non-central chi-squared distribution with
+// c = sigma^2 * (1 - e^(-kappa * dt)) / (4 * kappa),  d = 4 * kappa * theta / sigma^2 degrees of freedom,
+// non-centrality lambda = X(t) * e^(-kappa * dt) / c.
+// full truncation euler: cheaper, biased for large steps. the euler state may dip below zero,
+// only its positive part enters drift and diffusion and is emitted as the value.
+
+use num_traits::Float;
+use rand::Rng;
+
+use crate::base::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CirScheme {
+    #[default]
+    Exact, // sample the transition distribution. no discretisation error.
+    FullTruncationEuler, // euler-maruyama with negative values truncated to zero.
+}
+
+// kappa = 0 leaves the exact transition undefined, sigma = 0 divides by zero.
+fn assert_positive<T: Float>(kappa: T, theta: T, sigma: T) {
+    assert!(kappa > T::zero() && theta > T::zero() && sigma > T::zero(), "kappa, theta and sigma must be positive");
+}
+
+// gamma(shape, 1) sample, marsaglia-tsang. shape < 1 is boosted to shape + 1 and scaled back down.
+// drawn here instead of by rand_distr, whose samplers call the platform's exp and ln.
+fn gamma<R: Rng>(rng: &mut R, shape: f64) -> f64 {
+    if shape < 1.0 {
+        let u = 1.0 - uniform::<f64, R>(rng);
+        return gamma(rng, shape + 1.0) * powf(u, 1.0 / shape);
+    }
+    let d = shape - 1.0 / 3.0;
+    let c = 1.0 / (9.0 * d).sqrt();
+
+    loop {
+        let z: f64 = standard_normal(rng);
+        let v = 1.0 + c * z;
+        if v <= 0.0 {
+            continue;
+        }
+        let v = v * v * v;
+        let u = 1.0 - uniform::<f64, R>(rng);
+
+        if u < 1.0 - 0.0331 * z * z * z * z || ln(u) < 0.5 * z * z + d * (1.0 - v + ln(v)) {
+            return d * v;
+        }
+    }
+}
+
+// poisson(lambda) sample. small lambda: multiply uniforms until they drop below e^-lambda.
+// large lambda: transformed rejection (hoermann's ptrs), constant cost per sample.
+fn poisson<R: Rng>(rng: &mut R, lambda: f64) -> f64 {
+    if lambda < 10.0 {
+        let limit = exp(-lambda);
+        let mut product = uniform::<f64, R>(rng);
+        let mut count = 0.0;
+
+        while product > limit {
+            product *= uniform::<f64, R>(rng);
+            count += 1.0;
+        }
+        return count;
+    }
+
+    let root = lambda.sqrt();
+    let log_lambda = ln(lambda);
+    let b = 0.931 + 2.53 * root;
+    let a = -0.059 + 0.02483 * b;
+    let inv_alpha = 1.1239 + 1.1328 / (b - 3.4);
+    let v_r = 0.9277 - 3.6224 / (b - 2.0);
+
+    loop {
+        let u = uniform::<f64, R>(rng) - 0.5;
+        let v = uniform::<f64, R>(rng);
+        let us = 0.5 - u.abs();
+        let k = ((2.0 * a / us + b) * u + lambda + 0.43).floor();
+
+        if us >= 0.07 && v <= v_r {
+            return k;
+        }
+        if k < 0.0 || (us < 0.013 && v > us) {
+            continue;
+        }
+        if ln(v) + ln(inv_alpha) - ln(a / (us * us) + b) <= -lambda + k * log_lambda - libm::lgamma(k + 1.0) {
+            return k;
+        }
+    }
+}
+
+// sample of a central chi-squared distribution with `dof` > 0 degrees of freedom.
+fn chi_squared<R: Rng>(rng: &mut R, dof: f64) -> f64 {
+    2.0 * gamma(rng, 0.5 * dof)
+}
+
+// sample of a non-central chi-squared distribution with `dof` degrees of freedom.
+fn non_central_chi_squared<R: Rng>(rng: &mut R, dof: f64, lambda: f64) -> f64 {
+    if dof > 1.0 {
+        // sum of a shifted squared normal and a central chi-squared with dof - 1.
+        let z: f64 = standard_normal(rng);
+        let shifted = z + lambda.sqrt();
+
+        shifted * shifted + chi_squared(rng, dof - 1.0)
+    } else {
+        // poisson mixture of central chi-squared distributions.
+        let n = if lambda > 0.0 { poisson(rng, lambda / 2.0) } else { 0.0 };
+
+        chi_squared(rng, dof + 2.0 * n)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CoxIngersollRoss<T: Float = f64, R: Rng = DefaultRng, S: PathStorage<T> = Path<T>>
+{
+    path: S, // generated points
+
+    initial: Point<T>, // starting point. initial.y: starting rate, must be >= 0.
+
+    kappa: T, // speed of mean reversion, > 0
+    theta: T, // long-term level, > 0
+    sigma: T, // volatility, > 0
+
+    scheme: CirScheme,
+    euler_state: Option<T>, // unclipped state of the full truncation scheme. None: continue from last value.
+
+    step: T, // distance per loop/step.
+    remaining: u32, // steps left to generate. counted, so float rounding cannot add a step.
+    loops: u32, // steps per generation. reset restores remaining to it.
+
+    rng: R, // source of randomness. seeded -> reproducible path.
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> IntoIterator for CoxIngersollRoss<T, R, S> {
+    type Item = Point<T>;
+    type IntoIter = ProcessIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.path.into_iter()
+    }
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> Process for CoxIngersollRoss<T, R, S> {
+    type Scalar = T;
+    type Storage = S;
+
+    #[inline]
+    fn push_back(&mut self, point: Point<T>) -> usize {
+        self.euler_state = None;
+        self.path.push_back(point)
+    }
+    #[inline]
+    fn pop_back(&mut self) -> Option<Point<T>> {
+        self.euler_state = None;
+        self.path.pop_back()
+    }
+    fn path(&self) -> &S {
+        &self.path
+    }
+
+    // generate motion from given attributes
+    fn generate(&mut self) {
+        while self.remaining > 0 {
+            self.generate_single();
+        }
+    }
+    // generate single value
+    fn generate_single(&mut self) {
+        let cur = *self.path.tail().unwrap_or(&self.initial);
+
+        let (res, euler_state) = match self.scheme {
+            CirScheme::Exact => (self.exact_step(cur.y), None),
+            CirScheme::FullTruncationEuler => {
+                let state = self.full_truncation_step(self.euler_state.unwrap_or(cur.y));
+                (state.max(T::zero()), Some(state))
+            }
+        };
+
+        self.path.push_back( Point { x: cur.x + self.step, y: res } );
+        self.euler_state = euler_state;
+
+        self.remaining = self.remaining.saturating_sub(1);
+    }
+    fn reset(&mut self) {
+        self.path.clear();
+        self.euler_state = None;
+
+        self.remaining = self.loops;
+    }
+}
+
+impl<T: Float> CoxIngersollRoss<T> {
+    pub fn new(initial: Point<T>, kappa: T, theta: T, sigma: T, step: T, loops: u32) -> Self {
+        Self::with_rng(initial, kappa, theta, sigma, step, loops, default_rng())
+    }
+    // same seed and parameters -> identical path.
+    pub fn with_seed(initial: Point<T>, kappa: T, theta: T, sigma: T, step: T, loops: u32, seed: u64) -> Self {
+        Self::with_rng(initial, kappa, theta, sigma, step, loops, seeded_rng(seed))
+    }
+}
+
+impl<T: Float, R: Rng> CoxIngersollRoss<T, R> {
+    // panics unless kappa, theta and sigma are positive and the starting rate is not negative.
+    pub fn with_rng(initial: Point<T>, kappa: T, theta: T, sigma: T, step: T, loops: u32, rng: R) -> Self {
+        assert_positive(kappa, theta, sigma);
+        assert!(initial.y >= T::zero(), "starting rate must not be negative");
+
+        let cir = Self {
+            path: Path::with_capacity(loops as usize),
+
+            // model parameters
+            initial,
+            kappa,
+            theta,
+            sigma,
+
+            scheme: CirScheme::default(),
+            euler_state: None,
+
+            step,
+            remaining: loops,
+            loops,
+
+            rng,
+        };
+
+        if !cir.feller_condition() {
+            log::warn!("cox-ingersoll-ross: feller condition 2 * kappa * theta >= sigma^2 violated, zero is reachable");
+        }
+        cir
+    }
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> CoxIngersollRoss<T, R, S> {
+    // swap the storage backend. points generated so far are dropped, generation starts over from the initial point.
+    pub fn with_storage<U: PathStorage<T>>(self, storage: U) -> CoxIngersollRoss<T, R, U> {
+        CoxIngersollRoss {
+            path: storage,
+
+            initial: self.initial,
+            kappa: self.kappa,
+            theta: self.theta,
+            sigma: self.sigma,
+            scheme: self.scheme,
+            euler_state: None,
+            step: self.step,
+            remaining: self.loops,
+            loops: self.loops,
+            rng: self.rng,
+        }
+    }
+    // select the stepping scheme. exact by default.
+    pub fn with_scheme(mut self, scheme: CirScheme) -> Self {
+        self.scheme = scheme;
+        self
+    }
+    // exact sample of X(t + dt) given X(t) = value.
+    fn exact_step(&mut self, value: T) -> T {
+        let to_f64 = |v: T| v.to_f64().expect("scalar converts to f64");
+        let (kappa, theta, sigma, dt) = (to_f64(self.kappa), to_f64(self.theta), to_f64(self.sigma), to_f64(self.step));
+
+        let decay = exp(-kappa * dt);
+        let c = sigma * sigma * (1.0 - decay) / (4.0 * kappa);
+        let dof = 4.0 * kappa * theta / (sigma * sigma);
+        let lambda = to_f64(value.max(T::zero())) * decay / c;
+
+        scalar(c * non_central_chi_squared(&mut self.rng, dof, lambda))
+    }
+    // full truncation euler step of the (possibly negative) euler state.
+    fn full_truncation_step(&mut self, state: T) -> T {
+        let z: T = standard_normal(&mut self.rng);
+        let positive = state.max(T::zero());
+
+        state + self.kappa * (self.theta - positive) * self.step + self.sigma * (positive * self.step).sqrt() * z
+    }
+    // 2 * kappa * theta >= sigma^2: the process stays strictly positive.
+    pub fn feller_condition(&self) -> bool {
+        let two: T = scalar(2);
+
+        two * self.kappa * self.theta >= self.sigma * self.sigma
+    }
+    pub fn scheme(&self) -> CirScheme {
+        self.scheme
+    }
+    pub fn set_scheme(&mut self, scheme: CirScheme) {
+        self.scheme = scheme;
+        self.euler_state = None;
+    }
+    pub fn set_kappa(&mut self, kappa: T) {
+        assert_positive(kappa, self.theta, self.sigma);
+
+        self.kappa = kappa;
+    }
+    pub fn set_theta(&mut self, theta: T) {
+        assert_positive(self.kappa, theta, self.sigma);
+
+        self.theta = theta;
+    }
+    pub fn set_sigma(&mut self, sigma: T) {
+        assert_positive(self.kappa, self.theta, sigma);
+
+        self.sigma = sigma;
+    }
+    pub fn set_step(&mut self, step: T) {
+        self.step = step;
+    }
+}
@@ -0,0 +1,125 @@
+// monte carlo ensembles: many independent paths of the same process.
+// path i is generated with stream i of the seed, so the result only depends on seed and n,
+// not on the order or the thread in which paths are generated.
+
+use num_traits::Float;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::base::*;
+
+// generated paths stored back to back. rows: paths, columns: steps.
+// processes with a random number of points (e.g. poisson) give rows of different length.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ensemble<T: Float = f64> {
+    points: Vec<Point<T>>,
+    offsets: Vec<usize>, // start of path i at offsets[i], end at offsets[i + 1]
+}
+
+// generate n independent paths. `build` receives the rng of each path and returns the unrun process,
+// e.g. |rng| GeometricBrownianMotion::with_rng(initial, 0.05, 0.2, 0.01, 252, rng)
+pub fn simulate_paths<P, F>(n: usize, seed: u64, build: F) -> Ensemble<P::Scalar>
+where
+    P: Process,
+    F: FnMut(DefaultRng) -> P,
+{
+    Ensemble::simulate(n, seed, build)
+}
+
+// same as simulate_paths, with paths generated across the rayon thread pool.
+// identical result to simulate_paths for the same n, seed and build.
+#[cfg(feature = "parallel")]
+pub fn simulate_paths_parallel<P, F>(n: usize, seed: u64, build: F) -> Ensemble<P::Scalar>
+where
+    P: Process,
+    P::Scalar: Send,
+    F: Fn(DefaultRng) -> P + Sync,
+{
+    Ensemble::simulate_parallel(n, seed, build)
+}
+
+impl<T: Float> Ensemble<T> {
+    pub fn simulate<P, F>(n: usize, seed: u64, mut build: F) -> Self
+    where
+        P: Process<Scalar = T>,
+        F: FnMut(DefaultRng) -> P,
+    {
+        let mut ensemble = Self::default();
+        ensemble.offsets.reserve(n);
+
+        for i in 0..n {
+            let mut process = build(stream_rng(seed, i as u64));
+            process.generate();
+
+            ensemble.push_path(process.path().iter().copied());
+        }
+        ensemble
+    }
+    #[cfg(feature = "parallel")]
+    pub fn simulate_parallel<P, F>(n: usize, seed: u64, build: F) -> Self
+    where
+        T: Send,
+        P: Process<Scalar = T>,
+        F: Fn(DefaultRng) -> P + Sync,
+    {
+        // indexed collect keeps path order independent of scheduling.
+        let paths: Vec<Vec<Point<T>>> = (0..n)
+            .into_par_iter()
+            .map(|i| {
+                let mut process = build(stream_rng(seed, i as u64));
+                process.generate();
+
+                process.path().iter().copied().collect()
+            })
+            .collect();
+
+        let mut ensemble = Self::default();
+        ensemble.offsets.reserve(n);
+        ensemble.points.reserve(paths.iter().map(Vec::len).sum());
+
+        for path in paths {
+            ensemble.push_path(path);
+        }
+        ensemble
+    }
+    // append a path as the next row.
+    pub fn push_path<I: IntoIterator<Item = Point<T>>>(&mut self, path: I) {
+        self.points.extend(path);
+        self.offsets.push(self.points.len());
+    }
+    // number of paths
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    // number of steps of the longest path
+    pub fn steps(&self) -> usize {
+        self.offsets.windows(2).map(|w| w[1] - w[0]).max().unwrap_or(0)
+    }
+    // points of path i
+    pub fn path(&self, i: usize) -> &[Point<T>] {
+        &self.points[self.offsets[i]..self.offsets[i + 1]]
+    }
+    pub fn paths(&self) -> impl Iterator<Item = &[Point<T>]> + '_ {
+        (0..self.len()).map(move |i| self.path(i))
+    }
+    // point at index `step` of every path. paths that ended earlier are skipped.
+    pub fn time_slice(&self, step: usize) -> Vec<Point<T>> {
+        self.paths().filter_map(|path| path.get(step).copied()).collect()
+    }
+    // final y value of every path. empty paths are skipped.
+    pub fn terminal(&self) -> Vec<T> {
+        self.paths().filter_map(|path| path.last().map(|point| point.y)).collect()
+    }
+}
+
+impl<T: Float> Default for Ensemble<T> {
+    fn default() -> Self {
+        Self {
+            points: Vec::new(),
+            offsets: vec![0],
+        }
+    }
+}
@@ -0,0 +1,321 @@
+// --- theory :
+// fractional brownian motion B_H: gaussian, B_H(0) = 0, E[ B_H(t) B_H(s) ] = sigma^2 / 2 * ( t^2H + s^2H - |t - s|^2H ).
+// H = 0.5 is standard brownian motion. H > 0.5: increments positively correlated (smooth, trending).
+// H < 0.5: negatively correlated (rough, mean-reverting).
+// increments over a step dt (fractional gaussian noise) are stationary with autocovariance
+// gamma(k) = sigma^2 * dt^2H / 2 * ( |k + 1|^2H - 2 |k|^2H + |k - 1|^2H ).
+
+// --- here:
+// exact: davies-harte. embed the covariance of n increments in a circulant matrix of size 2N (N >= n, power of two),
+// whose eigenvalues come from an fft. all increments are drawn at once.
+// approximate: durbin-levinson (hosking) prediction from only the last `memory` increments.
+// exact while fewer than memory increments exist, drawn one by one at O(memory) per step.
+
+use std::collections::VecDeque;
+use std::f64::consts::PI;
+
+use num_traits::Float;
+use rand::Rng;
+
+use crate::base::*;
+
+// autocovariance of unit fractional gaussian noise (sigma = 1, dt = 1) at lag k.
+fn fgn_covariance(hurst: f64, k: usize) -> f64 {
+    let k = k as f64;
+    let two_h = 2.0 * hurst;
+
+    0.5 * ( powf(k + 1.0, two_h) - 2.0 * powf(k, two_h) + powf((k - 1.0).abs(), two_h) )
+}
+
+// in-place iterative radix-2 fft. length must be a power of two.
+fn fft(re: &mut [f64], im: &mut [f64]) {
+    let n = re.len();
+
+    // bit-reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let angle = -2.0 * PI / len as f64;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (w_re, w_im) = (cos(angle * k as f64), sin(angle * k as f64));
+                let (a, b) = (start + k, start + k + len / 2);
+
+                let t_re = re[b] * w_re - im[b] * w_im;
+                let t_im = re[b] * w_im + im[b] * w_re;
+
+                re[b] = re[a] - t_re;
+                im[b] = im[a] - t_im;
+                re[a] += t_re;
+                im[a] += t_im;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+// n samples of unit fractional gaussian noise via circulant embedding.
+fn davies_harte<R: Rng>(rng: &mut R, hurst: f64, n: usize) -> Vec<f64> {
+    let half = n.next_power_of_two();
+    let m = 2 * half;
+
+    // first row of the circulant matrix: gamma(0), .., gamma(N), gamma(N - 1), .., gamma(1)
+    let mut re: Vec<f64> = (0..m).map(|j| fgn_covariance(hurst, if j <= half { j } else { m - j })).collect();
+    let mut im = vec![0.0; m];
+    fft(&mut re, &mut im);
+
+    // eigenvalues are non-negative for fgn in theory. clamp rounding noise.
+    if re.iter().any(|&lambda| lambda < -1e-8) {
+        log::warn!("circulant embedding is not positive definite (H = {}), negative eigenvalues are set to 0", hurst);
+    }
+    let scale: Vec<f64> = re.iter().map(|&lambda| (lambda.max(0.0) / m as f64).sqrt()).collect();
+
+    // complex gaussian weights with hermitian symmetry -> real output.
+    let mut w_re = vec![0.0; m];
+    let mut w_im = vec![0.0; m];
+
+    w_re[0] = scale[0] * standard_normal::<f64, R>(rng);
+    w_re[half] = scale[half] * standard_normal::<f64, R>(rng);
+    for j in 1..half {
+        let s = scale[j] * std::f64::consts::FRAC_1_SQRT_2;
+        let (a, b) = (standard_normal::<f64, R>(rng), standard_normal::<f64, R>(rng));
+
+        w_re[j] = s * a;
+        w_im[j] = s * b;
+        w_re[m - j] = s * a;
+        w_im[m - j] = -s * b;
+    }
+    fft(&mut w_re, &mut w_im);
+
+    w_re.truncate(n);
+    w_re
+}
+
+// durbin-levinson coefficients for predicting one increment from up to memory previous ones.
+// row k: phi_k1 .. phi_kk (most recent first) and prediction variance v_k.
+fn levinson(hurst: f64, memory: usize) -> (Vec<Vec<f64>>, Vec<f64>) {
+    let mut phi: Vec<Vec<f64>> = vec![Vec::new()];
+    let mut variance = vec![fgn_covariance(hurst, 0)];
+
+    for k in 1..=memory {
+        let previous = &phi[k - 1];
+        let v = variance[k - 1];
+
+        let reflection = (fgn_covariance(hurst, k) - (1..k).map(|j| previous[j - 1] * fgn_covariance(hurst, k - j)).sum::<f64>()) / v;
+
+        let mut row: Vec<f64> = (1..k).map(|j| previous[j - 1] - reflection * previous[k - j - 1]).collect();
+        row.push(reflection);
+
+        phi.push(row);
+        variance.push(v * (1.0 - reflection * reflection));
+    }
+    (phi, variance)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FbmMethod {
+    // davies-harte circulant embedding: exact covariance, whole path drawn in O(n log n).
+    #[default]
+    Exact,
+    // hosking prediction truncated to the last memory increments. sequential, O(memory) per step.
+    // correlations up to memory lags are exact, longer-range behaviour drifts: raise memory for long paths.
+    Approximate { memory: usize },
+}
+
+#[derive(Debug, Clone)]
+pub struct FractionalBrownianMotion<T: Float = f64, R: Rng = DefaultRng, S: PathStorage<T> = Path<T>>
+{
+    path: S, // generated points
+
+    initial: Point<T>, // starting point
+
+    hurst: T, // H in (0, 1): 0.5 -> brownian motion, above -> persistent, below -> anti-persistent.
+    scale: T, // sigma: scales the increments.
+    method: FbmMethod, // exact or approximate generation
+
+    step: T, // distance per loop/step.
+    remaining: u32, // steps left to generate. counted, so float rounding cannot add a step.
+    loops: u32, // steps per generation. reset restores remaining to it.
+
+    pending: Vec<f64>, // exact: unit increments drawn in advance, last one used next.
+    history: VecDeque<f64>, // approximate: last unit increments, most recent first.
+    coefficients: (Vec<Vec<f64>>, Vec<f64>), // approximate: durbin-levinson rows and variances.
+
+    rng: R, // source of randomness. seeded -> reproducible path.
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> IntoIterator for FractionalBrownianMotion<T, R, S> {
+    type Item = Point<T>;
+    type IntoIter = ProcessIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.path.into_iter()
+    }
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> Process for FractionalBrownianMotion<T, R, S> {
+    type Scalar = T;
+    type Storage = S;
+
+    #[inline]
+    fn push_back(&mut self, point: Point<T>) -> usize {
+        self.path.push_back(point)
+    }
+    #[inline]
+    fn pop_back(&mut self) -> Option<Point<T>> {
+        self.path.pop_back()
+    }
+    fn path(&self) -> &S {
+        &self.path
+    }
+
+    // generate motion from given attributes
+    fn generate(&mut self) {
+        while self.remaining > 0 {
+            self.generate_single();
+        }
+    }
+    // generate single value
+    fn generate_single(&mut self) {
+        if self.remaining == 0 {
+            return;
+        }
+        let cur = *self.path.tail().unwrap_or(&self.initial);
+
+        let unit = self.next_unit_increment();
+        // self-similarity: increments over dt scale with dt^H.
+        let increment = self.scale * powf(self.step, self.hurst) * scalar(unit);
+
+        self.push_back( Point { x: cur.x + self.step, y: cur.y + increment } );
+
+        self.remaining = self.remaining.saturating_sub(1);
+    }
+    fn reset(&mut self) {
+        self.path.clear();
+
+        self.pending.clear();
+        self.history.clear();
+
+        self.remaining = self.loops;
+    }
+}
+
+impl<T: Float> FractionalBrownianMotion<T> {
+    pub fn new(initial: Point<T>, hurst: T, scale: T, step: T, loops: u32) -> Self {
+        Self::with_rng(initial, hurst, scale, step, loops, default_rng())
+    }
+    // same seed and parameters -> identical path.
+    pub fn with_seed(initial: Point<T>, hurst: T, scale: T, step: T, loops: u32, seed: u64) -> Self {
+        Self::with_rng(initial, hurst, scale, step, loops, seeded_rng(seed))
+    }
+}
+
+impl<T: Float, R: Rng> FractionalBrownianMotion<T, R> {
+    // exact generation. switch with with_method.
+    pub fn with_rng(initial: Point<T>, hurst: T, scale: T, step: T, loops: u32, rng: R) -> Self {
+        assert!(hurst > T::zero() && hurst < T::one(), "hurst exponent must lie in (0, 1)");
+
+        Self {
+            path: Path::with_capacity(loops as usize),
+
+            // model parameters
+            initial,
+            hurst,
+            scale,
+            method: FbmMethod::Exact,
+            step,
+            remaining: loops,
+            loops,
+
+            pending: Vec::new(),
+            history: VecDeque::new(),
+            coefficients: (Vec::new(), Vec::new()),
+
+            rng,
+        }
+    }
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> FractionalBrownianMotion<T, R, S> {
+    // swap the storage backend. points generated so far are dropped, generation starts over from the initial point.
+    pub fn with_storage<U: PathStorage<T>>(self, storage: U) -> FractionalBrownianMotion<T, R, U> {
+        FractionalBrownianMotion {
+            path: storage,
+
+            initial: self.initial,
+            hurst: self.hurst,
+            scale: self.scale,
+            method: self.method,
+            step: self.step,
+            remaining: self.loops,
+            loops: self.loops,
+            pending: Vec::new(),
+            history: VecDeque::new(),
+            coefficients: self.coefficients,
+            rng: self.rng,
+        }
+    }
+    pub fn with_method(mut self, method: FbmMethod) -> Self {
+        self.set_method(method);
+        self
+    }
+    // call before generating: increments drawn so far do not carry over to the new method.
+    pub fn set_method(&mut self, method: FbmMethod) {
+        if let FbmMethod::Approximate { memory } = method {
+            assert!(memory > 0, "approximate generation needs a memory of at least one increment");
+
+            self.coefficients = levinson(self.hurst.to_f64().unwrap_or(0.5), memory);
+        }
+        self.method = method;
+
+        self.pending.clear();
+        self.history.clear();
+    }
+    pub fn set_scale(&mut self, scale: T) {
+        self.scale = scale;
+    }
+    pub fn hurst(&self) -> T {
+        self.hurst
+    }
+    // next increment of unit fractional gaussian noise.
+    fn next_unit_increment(&mut self) -> f64 {
+        let hurst = self.hurst.to_f64().unwrap_or(0.5);
+
+        match self.method {
+            FbmMethod::Exact => {
+                // draw every remaining increment at once, so they share one covariance structure.
+                if self.pending.is_empty() {
+                    self.pending = davies_harte(&mut self.rng, hurst, self.remaining as usize);
+                    self.pending.reverse();
+                }
+                self.pending.pop().unwrap_or(0.0)
+            }
+            FbmMethod::Approximate { memory } => {
+                let (phi, variance) = &self.coefficients;
+                let order = self.history.len().min(memory);
+
+                let mean: f64 = phi[order].iter().zip(self.history.iter()).map(|(c, x)| c * x).sum();
+                let z: f64 = standard_normal(&mut self.rng);
+                let increment = mean + variance[order].max(0.0).sqrt() * z;
+
+                self.history.push_front(increment);
+                self.history.truncate(memory);
+
+                increment
+            }
+        }
+    }
+}
@@ -0,0 +1,228 @@
+// --- theory :
+// stochastic volatility: the variance of a gbm-like price is itself a cox-ingersoll-ross process.
+// dS = mu * S dt + sqrt(v) * S dW1
+// dv = kappa * (theta - v) dt + xi * sqrt(v) dW2,   corr(dW1, dW2) = rho
+// -> rho < 0 reproduces the leverage effect: falling prices come with rising volatility.
+
+// --- here:
+// log-euler step for the price, full truncation euler for the variance:
+// only the positive part of the variance enters drift and diffusion and is emitted.
+// correlated drivers: Z2 = rho * Z1 + sqrt(1 - rho^2) * Z3
+
+use num_traits::Float;
+use rand::Rng;
+
+use crate::base::*;
+
+// model parameters apart from starting values and step size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HestonParams<T: Float = f64> {
+    pub drift: T, // mu: drift rate of the price
+    pub kappa: T, // speed of mean reversion of the variance
+    pub theta: T, // long-term variance
+    pub xi: T, // volatility of the variance
+    pub rho: T, // correlation of the two brownian drivers, in [-1, 1]
+}
+
+// |rho| > 1 has no sqrt(1 - rho^2): the path would silently become NaN.
+// negative kappa, theta or xi turn the variance process into nonsense, like for cox-ingersoll-ross.
+// xi = 0 is allowed: deterministic variance.
+fn assert_params<T: Float>(params: &HestonParams<T>) {
+    assert!(params.rho >= -T::one() && params.rho <= T::one(), "correlation rho must lie in [-1, 1]");
+    assert!(
+        params.kappa >= T::zero() && params.theta >= T::zero() && params.xi >= T::zero(),
+        "kappa, theta and xi must not be negative"
+    );
+}
+
+// price and variance at the same x.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HestonPoint<T: Float = f64> {
+    pub x: T,
+    pub price: T,
+    pub variance: T,
+}
+
+// the process' own path holds the price. the variance path is kept alongside in the same storage type.
+#[derive(Debug, Clone)]
+pub struct Heston<T: Float = f64, R: Rng = DefaultRng, S: PathStorage<T> = Path<T>>
+{
+    path: S, // generated prices
+    variance: S, // generated variances, same x as the prices
+
+    initial: Point<T>, // starting point. initial.y: starting price
+    initial_variance: T, // starting variance, >= 0
+
+    params: HestonParams<T>,
+    euler_state: Option<T>, // unclipped variance of the full truncation scheme. None: continue from last value.
+
+    step: T, // distance per loop/step.
+    remaining: u32, // steps left to generate. counted, so float rounding cannot add a step.
+    loops: u32, // steps per generation. reset restores remaining to it.
+
+    rng: R, // source of randomness. seeded -> reproducible path.
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> IntoIterator for Heston<T, R, S> {
+    type Item = Point<T>;
+    type IntoIter = ProcessIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.path.into_iter()
+    }
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> Process for Heston<T, R, S> {
+    type Scalar = T;
+    type Storage = S;
+
+    // prices and variances are generated in pairs. a pushed price repeats the last variance.
+    #[inline]
+    fn push_back(&mut self, point: Point<T>) -> usize {
+        let variance = self.current_variance();
+
+        self.variance.push_back( Point { x: point.x, y: variance } );
+        self.euler_state = None;
+        self.path.push_back(point)
+    }
+    #[inline]
+    fn pop_back(&mut self) -> Option<Point<T>> {
+        self.variance.pop_back();
+        self.euler_state = None;
+        self.path.pop_back()
+    }
+    fn path(&self) -> &S {
+        &self.path
+    }
+
+    // generate motion from given attributes
+    fn generate(&mut self) {
+        while self.remaining > 0 {
+            self.generate_single();
+        }
+    }
+    // generate single value
+    fn generate_single(&mut self) {
+        let cur = *self.path.tail().unwrap_or(&self.initial);
+        let state = self.euler_state.unwrap_or_else(|| self.current_variance());
+
+        let p = self.params;
+        let half: T = scalar(0.5);
+        let positive = state.max(T::zero());
+
+        let z1: T = standard_normal(&mut self.rng);
+        let z3: T = standard_normal(&mut self.rng);
+        let z2 = p.rho * z1 + (T::one() - p.rho * p.rho).sqrt() * z3;
+
+        let diffusion = (positive * self.step).sqrt();
+
+        let price = cur.y * exp( (p.drift - half * positive) * self.step + diffusion * z1 );
+        let next_state = state + p.kappa * (p.theta - positive) * self.step + p.xi * diffusion * z2;
+
+        let x = cur.x + self.step;
+        self.path.push_back( Point { x, y: price } );
+        self.variance.push_back( Point { x, y: next_state.max(T::zero()) } );
+        self.euler_state = Some(next_state);
+
+        self.remaining = self.remaining.saturating_sub(1);
+    }
+    fn reset(&mut self) {
+        self.path.clear();
+        self.variance.clear();
+        self.euler_state = None;
+
+        self.remaining = self.loops;
+    }
+}
+
+impl<T: Float> Heston<T> {
+    pub fn new(initial: Point<T>, initial_variance: T, params: HestonParams<T>, step: T, loops: u32) -> Self {
+        Self::with_rng(initial, initial_variance, params, step, loops, default_rng())
+    }
+    // same seed and parameters -> identical path.
+    pub fn with_seed(initial: Point<T>, initial_variance: T, params: HestonParams<T>, step: T, loops: u32, seed: u64) -> Self {
+        Self::with_rng(initial, initial_variance, params, step, loops, seeded_rng(seed))
+    }
+}
+
+impl<T: Float, R: Rng> Heston<T, R> {
+    // panics if rho lies outside [-1, 1] or kappa, theta, xi or the starting variance is negative.
+    pub fn with_rng(initial: Point<T>, initial_variance: T, params: HestonParams<T>, step: T, loops: u32, rng: R) -> Self {
+        assert_params(&params);
+        assert!(initial_variance >= T::zero(), "starting variance must not be negative");
+
+        let heston = Self {
+            path: Path::with_capacity(loops as usize),
+            variance: Path::with_capacity(loops as usize),
+
+            // model parameters
+            initial,
+            initial_variance,
+            params,
+            euler_state: None,
+
+            step,
+            remaining: loops,
+            loops,
+
+            rng,
+        };
+
+        if !heston.feller_condition() {
+            log::warn!("heston: feller condition 2 * kappa * theta >= xi^2 violated, variance can reach zero");
+        }
+        heston
+    }
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> Heston<T, R, S> {
+    // swap the storage backend of both paths. points generated so far are dropped, generation starts over from the initial point.
+    pub fn with_storage<U: PathStorage<T> + Clone>(self, storage: U) -> Heston<T, R, U> {
+        Heston {
+            path: storage.clone(),
+            variance: storage,
+
+            initial: self.initial,
+            initial_variance: self.initial_variance,
+            params: self.params,
+            euler_state: None,
+            step: self.step,
+            remaining: self.loops,
+            loops: self.loops,
+            rng: self.rng,
+        }
+    }
+    // variance at the end of the generated path.
+    fn current_variance(&self) -> T {
+        self.variance.tail().map(|point| point.y).unwrap_or(self.initial_variance)
+    }
+    // generated variances. x values match the price path.
+    pub fn variance_path(&self) -> &S {
+        &self.variance
+    }
+    // price and variance, front to back.
+    pub fn points(&self) -> impl Iterator<Item = HestonPoint<T>> + '_ {
+        self.path.iter().zip(self.variance.iter()).map(|(price, variance)| HestonPoint {
+            x: price.x,
+            price: price.y,
+            variance: variance.y,
+        })
+    }
+    // 2 * kappa * theta >= xi^2: the variance stays strictly positive.
+    pub fn feller_condition(&self) -> bool {
+        let two: T = scalar(2);
+
+        two * self.params.kappa * self.params.theta >= self.params.xi * self.params.xi
+    }
+    pub fn params(&self) -> &HestonParams<T> {
+        &self.params
+    }
+    pub fn set_params(&mut self, params: HestonParams<T>) {
+        assert_params(&params);
+
+        self.params = params;
+    }
+    pub fn set_step(&mut self, step: T) {
+        self.step = step;
+    }
+}
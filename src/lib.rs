@@ -1,11 +1,27 @@
 pub mod base;
 
-pub mod brownian;
+pub mod storage;
+
+pub mod ensemble;
 
-pub mod brownian_vec;
+pub mod brownian;
 
 pub mod rwalk;
 
 pub mod bernoulli;
 
-pub mod poisson;
\ No newline at end of file
+pub mod poisson;
+
+pub mod ornstein;
+
+pub mod cir;
+
+pub mod heston;
+
+pub mod merton;
+
+pub mod markov;
+
+pub mod fractional;
+
+pub mod sde;
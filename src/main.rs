@@ -4,9 +4,7 @@ use process_lib::base::*;
 
 fn main() {
     let mut gbm =
-        GeometricBrownianMotion::new(Point {x: 0.0, y: 0.0}, 21.0, 8.0, 6.0, 10);
+        GeometricBrownianMotion::new(Point {x: 0.0, y: 100.0}, 0.05, 0.2, 1.0 / 252.0, 252);
 
     gbm.generate();
-
-    return ();
 }
\ No newline at end of file
@@ -0,0 +1,636 @@
+// --- theory :
+// finitely many states. the next state depends only on the current one:
+// P(X(n + 1) = j | X(n) = i) = p_ij.   every row of the transition matrix P sums to 1.
+// bernoulli trials and lattice random walks are special cases.
+
+// stationary distribution pi: pi * P = pi, sum(pi) = 1.   unique if the chain is irreducible.
+// expected hitting time of a target state: h_target = 0,  h_i = 1 + sum_j p_ij * h_j.
+
+// --- here:
+// states are indices 0..n. each state is mapped to a y-value, x advances by step per transition.
+// the state after every transition is kept alongside the path in the same storage type (y = state),
+// so a bounded backend bounds it too. history views cover the transitions the storage still holds.
+
+use num_traits::Float;
+use rand::Rng;
+
+use crate::base::*;
+use crate::poisson::exponential_gap;
+
+// sum of a row and sum of its magnitudes.
+fn row_sums<T: Float>(row: &[T]) -> (T, T) {
+    row.iter().fold((T::zero(), T::zero()), |(sum, scale), &v| (sum + v, scale + v.abs()))
+}
+
+// state index kept as y in a state storage.
+fn state_index<T: Float>(y: T) -> usize {
+    y.to_usize().expect("stored state is a valid index")
+}
+
+// gaussian elimination with partial pivoting. None if the system is singular.
+pub(crate) fn solve_linear<T: Float>(mut a: Vec<Vec<T>>, mut b: Vec<T>) -> Option<Vec<T>> {
+    let n = b.len();
+    let scale = a.iter().flatten().fold(T::zero(), |max, &v| max.max(v.abs()));
+    let eps = rounding_tolerance(n, scale);
+
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| a[i][col].abs().partial_cmp(&a[j][col].abs()).expect("matrix entries must not be NaN"))?;
+
+        if a[pivot][col].abs() < eps {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+
+        let (upper, lower) = a.split_at_mut(col + 1);
+        let pivot_row = &upper[col];
+
+        for (offset, row) in lower.iter_mut().enumerate() {
+            let factor = row[col] / pivot_row[col];
+            for (entry, &p) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                *entry = *entry - factor * p;
+            }
+            b[col + 1 + offset] = b[col + 1 + offset] - factor * b[col];
+        }
+    }
+
+    let mut x = vec![T::zero(); n];
+    for row in (0..n).rev() {
+        let sum = (row + 1..n).fold(b[row], |sum, k| sum - a[row][k] * x[k]);
+        x[row] = sum / a[row][row];
+    }
+    Some(x)
+}
+
+// distribution pi with pi * A = 0 and sum(pi) = 1.
+// A = P - I for discrete chains, A = Q for continuous ones.
+pub(crate) fn null_distribution<T: Float>(a: &[Vec<T>]) -> Option<Vec<T>> {
+    let n = a.len();
+
+    // transpose: pi * A = 0  <->  A^T * pi = 0. the last equation is redundant, replace it by sum(pi) = 1.
+    let mut system: Vec<Vec<T>> = (0..n).map(|i| (0..n).map(|j| a[j][i]).collect()).collect();
+    let mut rhs = vec![T::zero(); n];
+
+    system[n - 1] = vec![T::one(); n];
+    rhs[n - 1] = T::one();
+
+    solve_linear(system, rhs)
+}
+
+// states from which the target is reached with probability 1, given the possible moves of each state.
+// start from the states that can reach the target at all, then drop every state that can leave that set.
+pub(crate) fn certain_to_reach(moves: &[Vec<bool>], target: usize) -> Vec<bool> {
+    let n = moves.len();
+    let mut keep = vec![true; n];
+
+    loop {
+        // states that can reach the target within keep.
+        let mut reach = vec![false; n];
+        reach[target] = true;
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for i in 0..n {
+                if keep[i] && !reach[i] && (0..n).any(|j| moves[i][j] && reach[j]) {
+                    reach[i] = true;
+                    changed = true;
+                }
+            }
+        }
+
+        // a state that can move outside the set risks never arriving.
+        let next: Vec<bool> = (0..n)
+            .map(|i| reach[i] && (i == target || (0..n).all(|j| !moves[i][j] || reach[j])))
+            .collect();
+
+        if next == keep {
+            return keep;
+        }
+        keep = next;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MarkovChain<T: Float = f64, R: Rng = DefaultRng, S: PathStorage<T> = Path<T>>
+{
+    path: S, // generated points
+
+    transitions: Vec<Vec<T>>, // P: transitions[i][j] = probability of moving from state i to j
+    values: Vec<T>, // y-value of every state
+
+    initial_state: usize, // state before the first transition
+    start: T, // x of the initial state
+
+    step: T, // distance per transition.
+    remaining: u32, // steps left to generate. counted, so float rounding cannot add a step.
+    loops: u32, // steps per generation. reset restores remaining to it.
+
+    states: S, // state after every transition: y = state index, same x as the path
+    recorded: usize, // transitions recorded since reset, whether the storage still holds them or not
+
+    rng: R, // source of randomness. seeded -> reproducible path.
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> IntoIterator for MarkovChain<T, R, S> {
+    type Item = Point<T>;
+    type IntoIter = ProcessIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.path.into_iter()
+    }
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> Process for MarkovChain<T, R, S> {
+    type Scalar = T;
+    type Storage = S;
+
+    // a pushed point does not change the state: the chain continues from the current one.
+    #[inline]
+    fn push_back(&mut self, point: Point<T>) -> usize {
+        let state = self.state();
+
+        self.record(point, state)
+    }
+    #[inline]
+    fn pop_back(&mut self) -> Option<Point<T>> {
+        self.recorded = self.recorded.saturating_sub(1);
+        self.states.pop_back();
+        self.path.pop_back()
+    }
+    fn path(&self) -> &S {
+        &self.path
+    }
+    fn reset(&mut self) {
+        self.path.clear();
+        self.states.clear();
+        self.recorded = 0;
+
+        self.remaining = self.loops;
+    }
+    fn generate(&mut self) {
+        while self.remaining > 0 {
+            self.generate_single();
+        }
+    }
+    // generate single transition
+    fn generate_single(&mut self) {
+        let current = self.state();
+        let next = self.sample_next(current);
+
+        let x = self.path.tail().map(|point| point.x).unwrap_or(self.start) + self.step;
+
+        self.record( Point { x, y: self.values[next] }, next );
+
+        self.remaining = self.remaining.saturating_sub(1);
+    }
+}
+
+impl<T: Float> MarkovChain<T> {
+    pub fn new(transitions: Vec<Vec<T>>, values: Vec<T>, initial_state: usize, start: T, step: T, steps: u32) -> Self {
+        Self::with_rng(transitions, values, initial_state, start, step, steps, default_rng())
+    }
+    // same seed and parameters -> identical path.
+    pub fn with_seed(transitions: Vec<Vec<T>>, values: Vec<T>, initial_state: usize, start: T, step: T, steps: u32, seed: u64) -> Self {
+        Self::with_rng(transitions, values, initial_state, start, step, steps, seeded_rng(seed))
+    }
+}
+
+impl<T: Float, R: Rng> MarkovChain<T, R> {
+    // panics unless transitions is square, non-negative and every row sums to 1.
+    pub fn with_rng(transitions: Vec<Vec<T>>, values: Vec<T>, initial_state: usize, start: T, step: T, steps: u32, rng: R) -> Self {
+        let n = transitions.len();
+
+        assert!(n > 0, "a markov chain needs at least one state");
+        assert!(transitions.iter().all(|row| row.len() == n), "transition matrix must be square");
+        assert!(transitions.iter().flatten().all(|&p| p >= T::zero()), "transition probabilities must not be negative");
+        assert!(
+            transitions.iter().all(|row| {
+                let (sum, scale) = row_sums(row);
+                (sum - T::one()).abs() <= rounding_tolerance(n, scale)
+            }),
+            "every row of the transition matrix must sum to 1"
+        );
+        assert!(values.len() == n, "every state needs exactly one value");
+        assert!(initial_state < n, "initial state out of range");
+
+        Self {
+            path: Path::with_capacity(steps as usize),
+
+            // model parameters
+            transitions,
+            values,
+            initial_state,
+            start,
+            step,
+            remaining: steps,
+            loops: steps,
+
+            states: Path::with_capacity(steps as usize),
+            recorded: 0,
+
+            rng,
+        }
+    }
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> MarkovChain<T, R, S> {
+    // swap the storage backend. points generated so far are dropped, generation starts over from the initial point.
+    pub fn with_storage<U: PathStorage<T> + Clone>(self, storage: U) -> MarkovChain<T, R, U> {
+        MarkovChain {
+            path: storage.clone(),
+
+            transitions: self.transitions,
+            values: self.values,
+            initial_state: self.initial_state,
+            start: self.start,
+            step: self.step,
+            remaining: self.loops,
+            loops: self.loops,
+            states: storage,
+            recorded: 0,
+            rng: self.rng,
+        }
+    }
+    pub fn set_step(&mut self, step: T) {
+        self.step = step;
+    }
+    pub fn transitions(&self) -> &[Vec<T>] {
+        &self.transitions
+    }
+    // store a point and the state it belongs to.
+    fn record(&mut self, point: Point<T>, state: usize) -> usize {
+        self.states.push_back( Point { x: point.x, y: scalar(state) } );
+        self.recorded += 1;
+        self.path.push_back(point)
+    }
+    // current state: the last recorded one, the initial state before the first transition.
+    pub fn state(&self) -> usize {
+        self.states.tail().map(|point| state_index(point.y)).unwrap_or(self.initial_state)
+    }
+    // x and state after every transition kept by the storage
+    pub fn states(&self) -> impl Iterator<Item = (T, usize)> + '_ {
+        self.states.iter().map(|point| (point.x, state_index(point.y)))
+    }
+    // state storage: y = state index.
+    pub fn state_path(&self) -> &S {
+        &self.states
+    }
+    // the storage still holds every transition since the initial state.
+    fn full_history(&self) -> bool {
+        self.states.len() == self.recorded
+    }
+    // inverse transform over the row of the current state.
+    fn sample_next(&mut self, current: usize) -> usize {
+        let row = &self.transitions[current];
+        let u: T = uniform(&mut self.rng);
+
+        let mut cumulative = T::zero();
+        for (j, &p) in row.iter().enumerate() {
+            cumulative = cumulative + p;
+            if u < cumulative {
+                return j;
+            }
+        }
+        // row sums slightly below 1: fall back to the last reachable state.
+        row.iter().rposition(|&p| p > T::zero()).unwrap_or(current)
+    }
+    // pi with pi * P = pi. None if it is not unique (reducible chain).
+    pub fn stationary_distribution(&self) -> Option<Vec<T>> {
+        let n = self.transitions.len();
+        let generator: Vec<Vec<T>> = (0..n)
+            .map(|i| (0..n).map(|j| if i == j { self.transitions[i][j] - T::one() } else { self.transitions[i][j] }).collect())
+            .collect();
+
+        null_distribution(&generator)
+    }
+    // expected number of transitions until target is first entered, for every starting state.
+    // 0 for target itself, infinite if the target might never be reached.
+    pub fn expected_hitting_times(&self, target: usize) -> Vec<T> {
+        let n = self.transitions.len();
+        assert!(target < n, "target state out of range");
+
+        let moves: Vec<Vec<bool>> = self.transitions.iter().map(|row| row.iter().map(|&p| p > T::zero()).collect()).collect();
+        let finite = certain_to_reach(&moves, target);
+
+        // unknowns: states that surely arrive, except the target.
+        let unknowns: Vec<usize> = (0..n).filter(|&i| finite[i] && i != target).collect();
+
+        // (I - P) * h = 1 restricted to the unknowns. h_target = 0 drops out.
+        let system: Vec<Vec<T>> = unknowns
+            .iter()
+            .map(|&i| unknowns.iter().map(|&j| if i == j { T::one() - self.transitions[i][j] } else { -self.transitions[i][j] }).collect())
+            .collect();
+        let solution = solve_linear(system, vec![T::one(); unknowns.len()]).expect("hitting time system is non-singular");
+
+        let mut times = vec![T::infinity(); n];
+        times[target] = T::zero();
+        for (k, &i) in unknowns.iter().enumerate() {
+            times[i] = solution[k];
+        }
+        times
+    }
+    // x of the first kept transition into state. None if the storage holds no visit.
+    pub fn hitting_time(&self, state: usize) -> Option<T> {
+        self.states().find(|&(_, s)| s == state).map(|(x, _)| x)
+    }
+    // maximum-likelihood estimate of P from the kept transitions.
+    // the one out of the initial state counts only while the storage still holds the first transition.
+    // rows of states that were never left are zero.
+    pub fn empirical_transitions(&self) -> Vec<Vec<T>> {
+        let n = self.transitions.len();
+        let mut counts = vec![vec![0usize; n]; n];
+
+        let mut previous = if self.full_history() { Some(self.initial_state) } else { None };
+        for (_, state) in self.states() {
+            if let Some(previous) = previous {
+                counts[previous][state] += 1;
+            }
+            previous = Some(state);
+        }
+
+        counts
+            .iter()
+            .map(|row| {
+                let total: usize = row.iter().sum();
+                row.iter().map(|&c| if total == 0 { T::zero() } else { scalar::<T, _>(c) / scalar(total) }).collect()
+            })
+            .collect()
+    }
+}
+
+// --- theory :
+// continuous time: a chain stays in state i for an exponential holding time with rate q_i = -q_ii,
+// then jumps to j != i with probability q_ij / q_i.   rows of the generator Q sum to 0.
+// stationary distribution pi: pi * Q = 0, sum(pi) = 1.
+
+// --- here:
+// gillespie simulation. like Poisson, a point is added at every jump: x = start + time of the jump,
+// y = value of the new state. the path is constant in between.
+// the new state of every jump is kept alongside in the same storage type (y = state), like the discrete chain.
+#[derive(Debug, Clone)]
+pub struct ContinuousTimeMarkovChain<T: Float = f64, R: Rng = DefaultRng, S: PathStorage<T> = Path<T>>
+{
+    path: S, // generated points
+
+    generator: Vec<Vec<T>>, // Q: generator[i][j] = rate of jumping from state i to j, diagonal = -(sum of the row)
+    values: Vec<T>, // y-value of every state
+
+    initial_state: usize, // state at start
+    start: T, // x at which the chain starts
+
+    current_dist: T, // current time ( -> as total distance )
+    total_dist: T, // total time used for generation. ( -> as total distance.)
+
+    jumps: S, // new state of every jump: y = state index, same x as the path
+    recorded: usize, // jumps recorded since reset, whether the storage still holds them or not
+
+    rng: R, // source of randomness. seeded -> reproducible path.
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> IntoIterator for ContinuousTimeMarkovChain<T, R, S> {
+    type Item = Point<T>;
+    type IntoIter = ProcessIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.path.into_iter()
+    }
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> Process for ContinuousTimeMarkovChain<T, R, S> {
+    type Scalar = T;
+    type Storage = S;
+
+    // a pushed point does not change the state: the chain continues from the current one.
+    #[inline]
+    fn push_back(&mut self, point: Point<T>) -> usize {
+        let state = self.state();
+
+        self.record(point, state)
+    }
+    #[inline]
+    fn pop_back(&mut self) -> Option<Point<T>> {
+        self.recorded = self.recorded.saturating_sub(1);
+        self.jumps.pop_back();
+        self.path.pop_back()
+    }
+    fn path(&self) -> &S {
+        &self.path
+    }
+    fn reset(&mut self) {
+        self.path.clear();
+        self.jumps.clear();
+        self.recorded = 0;
+
+        self.current_dist = T::zero();
+    }
+    // generate jumps until total distance is reached.
+    fn generate(&mut self) {
+        while self.current_dist < self.total_dist {
+            self.generate_single();
+        }
+    }
+    // generate single jump.
+    fn generate_single(&mut self) {
+        let current = self.state();
+        let rate = -self.generator[current][current];
+
+        // absorbing state: no further jumps.
+        if rate <= T::zero() {
+            self.current_dist = self.total_dist;
+            return;
+        }
+
+        self.current_dist = self.current_dist + exponential_gap(&mut self.rng, T::one() / rate);
+
+        // next jump lies beyond total distance -> chain stays in its state until the end.
+        if self.current_dist > self.total_dist {
+            self.current_dist = self.total_dist;
+            return;
+        }
+
+        let next = self.sample_next(current, rate);
+        let x = self.start + self.current_dist;
+
+        self.record( Point { x, y: self.values[next] }, next );
+    }
+}
+
+impl<T: Float> ContinuousTimeMarkovChain<T> {
+    pub fn new(generator: Vec<Vec<T>>, values: Vec<T>, initial_state: usize, start: T, total_dist: T) -> Self {
+        Self::with_rng(generator, values, initial_state, start, total_dist, default_rng())
+    }
+    // same seed and parameters -> identical path.
+    pub fn with_seed(generator: Vec<Vec<T>>, values: Vec<T>, initial_state: usize, start: T, total_dist: T, seed: u64) -> Self {
+        Self::with_rng(generator, values, initial_state, start, total_dist, seeded_rng(seed))
+    }
+}
+
+impl<T: Float, R: Rng> ContinuousTimeMarkovChain<T, R> {
+    // panics unless generator is square, non-negative off the diagonal and every row sums to 0.
+    pub fn with_rng(generator: Vec<Vec<T>>, values: Vec<T>, initial_state: usize, start: T, total_dist: T, rng: R) -> Self {
+        let n = generator.len();
+
+        assert!(n > 0, "a markov chain needs at least one state");
+        assert!(generator.iter().all(|row| row.len() == n), "generator matrix must be square");
+        assert!(
+            generator.iter().enumerate().all(|(i, row)| row.iter().enumerate().all(|(j, &q)| i == j || q >= T::zero())),
+            "jump rates must not be negative"
+        );
+        assert!(
+            generator.iter().all(|row| {
+                let (sum, scale) = row_sums(row);
+                sum.abs() <= rounding_tolerance(n, scale)
+            }),
+            "every row of the generator matrix must sum to 0"
+        );
+        assert!(values.len() == n, "every state needs exactly one value");
+        assert!(initial_state < n, "initial state out of range");
+
+        Self {
+            path: Path::new(),
+
+            // model parameters
+            generator,
+            values,
+            initial_state,
+            start,
+            current_dist: T::zero(),
+            total_dist,
+
+            jumps: Path::new(),
+            recorded: 0,
+
+            rng,
+        }
+    }
+    // states 0..=n with value = state. birth[i]: rate from i to i + 1, death[i]: rate from i + 1 to i.
+    pub fn birth_death(birth: &[T], death: &[T], initial_state: usize, start: T, total_dist: T, rng: R) -> Self {
+        assert!(birth.len() == death.len(), "every birth rate needs a matching death rate");
+
+        let n = birth.len() + 1;
+        let mut generator = vec![vec![T::zero(); n]; n];
+
+        for i in 0..n - 1 {
+            generator[i][i + 1] = birth[i];
+            generator[i + 1][i] = death[i];
+        }
+        for (i, row) in generator.iter_mut().enumerate() {
+            row[i] = -row.iter().fold(T::zero(), |sum, &q| sum + q);
+        }
+        let values = (0..n).map(scalar).collect();
+
+        Self::with_rng(generator, values, initial_state, start, total_dist, rng)
+    }
+    // m/m/1/k queue: customers arrive at rate arrival, one server handles them at rate service.
+    // y = customers in the system, at most k = capacity. arrivals to a full system are turned away.
+    // the state space is finite, so the queue is stable even for arrival >= service.
+    pub fn mm1k_queue(arrival: T, service: T, capacity: usize, initial_state: usize, start: T, total_dist: T, rng: R) -> Self {
+        Self::birth_death(&vec![arrival; capacity], &vec![service; capacity], initial_state, start, total_dist, rng)
+    }
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> ContinuousTimeMarkovChain<T, R, S> {
+    // swap the storage backend. points generated so far are dropped, generation starts over from the initial point.
+    pub fn with_storage<U: PathStorage<T> + Clone>(self, storage: U) -> ContinuousTimeMarkovChain<T, R, U> {
+        ContinuousTimeMarkovChain {
+            path: storage.clone(),
+
+            generator: self.generator,
+            values: self.values,
+            initial_state: self.initial_state,
+            start: self.start,
+            current_dist: T::zero(),
+            total_dist: self.total_dist,
+            jumps: storage,
+            recorded: 0,
+            rng: self.rng,
+        }
+    }
+    pub fn set_total_dist(&mut self, total_dist: T) {
+        self.total_dist = total_dist;
+    }
+    pub fn generator(&self) -> &[Vec<T>] {
+        &self.generator
+    }
+    // store a point and the state it belongs to.
+    fn record(&mut self, point: Point<T>, state: usize) -> usize {
+        self.jumps.push_back( Point { x: point.x, y: scalar(state) } );
+        self.recorded += 1;
+        self.path.push_back(point)
+    }
+    // current state: the one entered by the last jump, the initial state before the first jump.
+    pub fn state(&self) -> usize {
+        self.jumps.tail().map(|point| state_index(point.y)).unwrap_or(self.initial_state)
+    }
+    // x and new state of every jump kept by the storage
+    pub fn jumps(&self) -> impl Iterator<Item = (T, usize)> + '_ {
+        self.jumps.iter().map(|point| (point.x, state_index(point.y)))
+    }
+    // jump storage: y = state index.
+    pub fn state_path(&self) -> &S {
+        &self.jumps
+    }
+    // the storage still holds every jump since start.
+    fn full_history(&self) -> bool {
+        self.jumps.len() == self.recorded
+    }
+    // state occupied at x. jumps take effect at their own x.
+    // None if x lies before the first jump the storage still holds.
+    pub fn state_at(&self, x: T) -> Option<usize> {
+        let mut state = if self.full_history() { Some(self.initial_state) } else { None };
+
+        for (jump, next) in self.jumps() {
+            if jump > x {
+                break;
+            }
+            state = Some(next);
+        }
+        state
+    }
+    // jump target with probability q_ij / q_i.
+    fn sample_next(&mut self, current: usize, rate: T) -> usize {
+        let row = &self.generator[current];
+        let u = uniform::<T, R>(&mut self.rng) * rate;
+
+        let mut cumulative = T::zero();
+        for (j, &q) in row.iter().enumerate() {
+            if j == current {
+                continue;
+            }
+            cumulative = cumulative + q;
+            if u < cumulative {
+                return j;
+            }
+        }
+        // rounding: fall back to the last reachable state.
+        row.iter().enumerate().rposition(|(j, &q)| j != current && q > T::zero()).unwrap_or(current)
+    }
+    // total x spent in every state up to the generated distance, counted from start while the storage
+    // still holds every jump, from the first kept jump otherwise.
+    pub fn occupation_times(&self) -> Vec<T> {
+        let mut times = vec![T::zero(); self.generator.len()];
+        let mut jumps = self.jumps();
+
+        let (mut since, mut state) = if self.full_history() {
+            (self.start, self.initial_state)
+        } else {
+            match jumps.next() {
+                Some(first) => first,
+                None => return times,
+            }
+        };
+        for (x, next) in jumps {
+            times[state] = times[state] + (x - since);
+            state = next;
+            since = x;
+        }
+        times[state] = times[state] + (self.start + self.current_dist - since);
+
+        times
+    }
+    // pi with pi * Q = 0. None if it is not unique (reducible chain).
+    pub fn stationary_distribution(&self) -> Option<Vec<T>> {
+        null_distribution(&self.generator)
+    }
+}
@@ -0,0 +1,202 @@
+// --- theory :
+// gbm with sudden jumps. jumps arrive as a poisson process with intensity lambda,
+// each one multiplies the value by a log-normal factor J = e^(m + delta * Z).
+// dS / S = (mu - lambda * k) dt + sigma dW + (J - 1) dN,   k = E[J - 1] = e^(m + delta^2 / 2) - 1
+// -> the compensator lambda * k keeps the expected growth at mu: E[S(t)] = S(0) * e^(mu * t).
+
+// --- here:
+// diffusion: exact gbm step with the compensated drift.
+// jumps: arrival positions on the x axis from the poisson module's exponential gaps.
+// every arrival inside a step is applied at the end of that step.
+// applied jumps are kept in a second storage of the same type (x = arrival, y = factor J),
+// so a bounded backend bounds them too. a step may hold several jumps or none.
+
+use num_traits::Float;
+use rand::Rng;
+
+use crate::base::*;
+use crate::brownian::gbm_step;
+use crate::poisson::exponential_gap;
+
+// model parameters apart from starting value and step size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MertonParams<T: Float = f64> {
+    pub drift: T, // mu: expected growth rate, jumps included
+    pub volatility: T, // sigma: volatility of the diffusion part
+    pub intensity: T, // lambda: average number of jumps per unit of x. 0: plain gbm.
+    pub jump_mean: T, // m: mean of the log jump size
+    pub jump_volatility: T, // delta: standard deviation of the log jump size
+}
+
+#[derive(Debug, Clone)]
+pub struct MertonJumpDiffusion<T: Float = f64, R: Rng = DefaultRng, S: PathStorage<T> = Path<T>>
+{
+    path: S, // generated points
+
+    initial: Point<T>, // starting point
+
+    params: MertonParams<T>,
+
+    next_jump: Option<T>, // x of the next arrival. None: not drawn yet.
+    jumps: S, // applied jumps: x = arrival, y = jump factor J
+
+    step: T, // distance per loop/step.
+    remaining: u32, // steps left to generate. counted, so float rounding cannot add a step.
+    loops: u32, // steps per generation. reset restores remaining to it.
+
+    rng: R, // source of randomness. seeded -> reproducible path.
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> IntoIterator for MertonJumpDiffusion<T, R, S> {
+    type Item = Point<T>;
+    type IntoIter = ProcessIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.path.into_iter()
+    }
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> Process for MertonJumpDiffusion<T, R, S> {
+    type Scalar = T;
+    type Storage = S;
+
+    #[inline]
+    fn push_back(&mut self, point: Point<T>) -> usize {
+        self.path.push_back(point)
+    }
+    // jumps after the new end of the path are dropped. arrivals are memoryless, so the next one is redrawn.
+    #[inline]
+    fn pop_back(&mut self) -> Option<Point<T>> {
+        let popped = self.path.pop_back();
+        let end = self.path.tail().unwrap_or(&self.initial).x;
+
+        while self.jumps.tail().is_some_and(|jump| jump.x > end) {
+            self.jumps.pop_back();
+        }
+        self.next_jump = None;
+
+        popped
+    }
+    fn path(&self) -> &S {
+        &self.path
+    }
+
+    // generate motion from given attributes
+    fn generate(&mut self) {
+        while self.remaining > 0 {
+            self.generate_single();
+        }
+    }
+    // generate single value
+    fn generate_single(&mut self) {
+        let cur = *self.path.tail().unwrap_or(&self.initial);
+        let x = cur.x + self.step;
+
+        let p = self.params;
+        let drift = p.drift - p.intensity * self.jump_compensator();
+        let mut res = gbm_step(&mut self.rng, cur.y, drift, p.volatility, self.step);
+
+        if p.intensity > T::zero() {
+            let mut next_jump = match self.next_jump {
+                Some(next_jump) => next_jump,
+                None => cur.x + exponential_gap(&mut self.rng, T::one() / p.intensity),
+            };
+
+            // apply every arrival up to the end of this step.
+            while next_jump <= x {
+                let z: T = standard_normal(&mut self.rng);
+                let factor = exp(p.jump_mean + p.jump_volatility * z);
+                res = res * factor;
+
+                self.jumps.push_back( Point { x: next_jump, y: factor } );
+                next_jump = next_jump + exponential_gap(&mut self.rng, T::one() / p.intensity);
+            }
+            self.next_jump = Some(next_jump);
+        }
+
+        self.path.push_back( Point { x, y: res } );
+
+        self.remaining = self.remaining.saturating_sub(1);
+    }
+    fn reset(&mut self) {
+        self.path.clear();
+        self.jumps.clear();
+        self.next_jump = None;
+
+        self.remaining = self.loops;
+    }
+}
+
+impl<T: Float> MertonJumpDiffusion<T> {
+    pub fn new(initial: Point<T>, params: MertonParams<T>, step: T, loops: u32) -> Self {
+        Self::with_rng(initial, params, step, loops, default_rng())
+    }
+    // same seed and parameters -> identical path.
+    pub fn with_seed(initial: Point<T>, params: MertonParams<T>, step: T, loops: u32, seed: u64) -> Self {
+        Self::with_rng(initial, params, step, loops, seeded_rng(seed))
+    }
+}
+
+impl<T: Float, R: Rng> MertonJumpDiffusion<T, R> {
+    pub fn with_rng(initial: Point<T>, params: MertonParams<T>, step: T, loops: u32, rng: R) -> Self {
+        Self {
+            path: Path::with_capacity(loops as usize),
+
+            // model parameters
+            initial,
+            params,
+
+            next_jump: None,
+            jumps: Path::new(),
+
+            step,
+            remaining: loops,
+            loops,
+
+            rng,
+        }
+    }
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> MertonJumpDiffusion<T, R, S> {
+    // swap the storage backend of path and jumps. points generated so far are dropped, generation starts over from the initial point.
+    pub fn with_storage<U: PathStorage<T> + Clone>(self, storage: U) -> MertonJumpDiffusion<T, R, U> {
+        MertonJumpDiffusion {
+            path: storage.clone(),
+
+            initial: self.initial,
+            params: self.params,
+            next_jump: None,
+            jumps: storage,
+            step: self.step,
+            remaining: self.loops,
+            loops: self.loops,
+            rng: self.rng,
+        }
+    }
+    // k = E[J - 1]: expected relative jump size.
+    fn jump_compensator(&self) -> T {
+        let half: T = scalar(0.5);
+        let p = &self.params;
+
+        exp(p.jump_mean + half * p.jump_volatility * p.jump_volatility) - T::one()
+    }
+    // x of every applied jump kept by the storage, in order.
+    pub fn jump_times(&self) -> impl Iterator<Item = T> + '_ {
+        self.jumps.iter().map(|jump| jump.x)
+    }
+    // applied jumps: x = arrival, y = jump factor J.
+    pub fn jumps(&self) -> &S {
+        &self.jumps
+    }
+    pub fn params(&self) -> &MertonParams<T> {
+        &self.params
+    }
+    pub fn set_params(&mut self, params: MertonParams<T>) {
+        self.params = params;
+        self.next_jump = None;
+    }
+    pub fn set_step(&mut self, step: T) {
+        self.step = step;
+    }
+}
@@ -0,0 +1,162 @@
+// --- theory :
+// mean-reverting process. the further the value is from the level mu, the stronger it is pulled back.
+// dX = theta * (mu - X) dt + sigma dW
+// -> stays near mu: interest rates, spreads, or a "wobble" around a resting position in games.
+
+// --- here:
+// exact discretisation instead of euler: for any step size dt,
+// X(t + dt) = mu + (X(t) - mu) * e^(-theta * dt) + sigma * sqrt( (1 - e^(-2 * theta * dt)) / (2 * theta) ) * Z,  Z ~ N(0, 1)
+// stationary distribution: N( mu, sigma^2 / (2 * theta) )
+
+use num_traits::Float;
+use rand::Rng;
+
+use crate::base::*;
+
+#[derive(Debug, Clone)]
+pub struct OrnsteinUhlenbeck<T: Float = f64, R: Rng = DefaultRng, S: PathStorage<T> = Path<T>>
+{
+    path: S, // generated points
+
+    initial: Point<T>, // starting point
+
+    theta: T, // speed of mean reversion
+    mu: T, // long-term level the process reverts to
+    sigma: T, // volatility of the noise
+
+    step: T, // distance per loop/step.
+    remaining: u32, // steps left to generate. counted, so float rounding cannot add a step.
+    loops: u32, // steps per generation. reset restores remaining to it.
+
+    rng: R, // source of randomness. seeded -> reproducible path.
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> IntoIterator for OrnsteinUhlenbeck<T, R, S> {
+    type Item = Point<T>;
+    type IntoIter = ProcessIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.path.into_iter()
+    }
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> Process for OrnsteinUhlenbeck<T, R, S> {
+    type Scalar = T;
+    type Storage = S;
+
+    #[inline]
+    fn push_back(&mut self, point: Point<T>) -> usize {
+        self.path.push_back(point)
+    }
+    #[inline]
+    fn pop_back(&mut self) -> Option<Point<T>> {
+        self.path.pop_back()
+    }
+    fn path(&self) -> &S {
+        &self.path
+    }
+
+    // generate motion from given attributes
+    fn generate(&mut self) {
+        while self.remaining > 0 {
+            self.generate_single();
+        }
+    }
+    // generate single value
+    fn generate_single(&mut self) {
+        let cur = *self.path.tail().unwrap_or(&self.initial);
+
+        let z: T = standard_normal(&mut self.rng);
+        let decay = exp(-self.theta * self.step);
+
+        let res = self.mu + (cur.y - self.mu) * decay + self.step_deviation() * z;
+
+        self.push_back( Point { x: cur.x + self.step, y: res } );
+
+        self.remaining = self.remaining.saturating_sub(1);
+    }
+    fn reset(&mut self) {
+        self.path.clear();
+
+        self.remaining = self.loops;
+    }
+}
+
+impl<T: Float> OrnsteinUhlenbeck<T> {
+    pub fn new(initial: Point<T>, theta: T, mu: T, sigma: T, step: T, loops: u32) -> Self {
+        Self::with_rng(initial, theta, mu, sigma, step, loops, default_rng())
+    }
+    // same seed and parameters -> identical path.
+    pub fn with_seed(initial: Point<T>, theta: T, mu: T, sigma: T, step: T, loops: u32, seed: u64) -> Self {
+        Self::with_rng(initial, theta, mu, sigma, step, loops, seeded_rng(seed))
+    }
+}
+
+impl<T: Float, R: Rng> OrnsteinUhlenbeck<T, R> {
+    pub fn with_rng(initial: Point<T>, theta: T, mu: T, sigma: T, step: T, loops: u32, rng: R) -> Self {
+        Self {
+            path: Path::with_capacity(loops as usize),
+
+            // model parameters
+            initial,
+            theta,
+            mu,
+            sigma,
+            step,
+            remaining: loops,
+            loops,
+
+            rng,
+        }
+    }
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> OrnsteinUhlenbeck<T, R, S> {
+    // swap the storage backend. points generated so far are dropped, generation starts over from the initial point.
+    pub fn with_storage<U: PathStorage<T>>(self, storage: U) -> OrnsteinUhlenbeck<T, R, U> {
+        OrnsteinUhlenbeck {
+            path: storage,
+
+            initial: self.initial,
+            theta: self.theta,
+            mu: self.mu,
+            sigma: self.sigma,
+            step: self.step,
+            remaining: self.loops,
+            loops: self.loops,
+            rng: self.rng,
+        }
+    }
+    // standard deviation of the noise added in one step.
+    // theta = 0: no reversion, the process is a scaled brownian motion.
+    fn step_deviation(&self) -> T {
+        if self.theta == T::zero() {
+            return self.sigma * self.step.sqrt();
+        }
+        let two: T = scalar(2);
+
+        self.sigma * ( (T::one() - exp(-two * self.theta * self.step)) / (two * self.theta) ).sqrt()
+    }
+    // mean of the stationary distribution
+    pub fn stationary_mean(&self) -> T {
+        self.mu
+    }
+    // variance of the stationary distribution. infinite without mean reversion.
+    pub fn stationary_variance(&self) -> T {
+        let two: T = scalar(2);
+
+        self.sigma * self.sigma / (two * self.theta)
+    }
+    pub fn set_theta(&mut self, theta: T) {
+        self.theta = theta;
+    }
+    pub fn set_mu(&mut self, mu: T) {
+        self.mu = mu;
+    }
+    pub fn set_sigma(&mut self, sigma: T) {
+        self.sigma = sigma;
+    }
+    pub fn set_step(&mut self, step: T) {
+        self.step = step;
+    }
+}
@@ -15,35 +15,500 @@ P(k events in time period) = e^(-events/time * time_period) * ...
 
 */
 
+use num_traits::Float;
 use rand::Rng;
+use rand_distr::Distribution;
 
 use crate::base::*;
+// jump distributions for CompoundPoisson. CompoundPoisson with Constant(1) counts like Poisson.
+pub use crate::base::{Constant, FromFn};
 
+// exponential inter-arrival gap with mean average_dist (inverse transform sampling).
+// shared by every process whose events arrive as a poisson process.
+pub(crate) fn exponential_gap<T: Float, R: Rng>(rng: &mut R, average_dist: T) -> T {
+    // 1 - U lies in (0, 1], so ln() never sees zero.
+    let sample: T = T::one() - uniform(rng);
+
+    -ln(sample) * average_dist
+}
+
+// a zero gap never advances the clock, so generate() would not terminate.
+fn assert_average_dist<T: Float>(average_dist: T) {
+    assert!(average_dist > T::zero() && average_dist.is_finite(), "average distance must be positive and finite");
+}
+
+#[derive(Debug, Clone)]
+
+pub struct Poisson<T: Float = f64, R: Rng = DefaultRng, S: PathStorage<T> = Path<T>>
+{
+    path: S, // generated points
+
+    initial: Point<T>, // starting point
+
+    current_dist: T, // current time ( -> as total distance )
+    average_dist: T, // average time it takes for event to occur. ( -> as average distance )
+    total_dist: T, // total time used for generation. ( -> as total distance.)
+
+    rng: R, // source of randomness. seeded -> reproducible path.
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> IntoIterator for Poisson<T, R, S> {
+    type Item = Point<T>;
+    type IntoIter = ProcessIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.path.into_iter()
+    }
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> Process for Poisson<T, R, S> {
+    type Scalar = T;
+    type Storage = S;
+
+    #[inline]
+    fn push_back(&mut self, point: Point<T>) -> usize {
+        self.path.push_back(point)
+    }
+    #[inline]
+    fn pop_back(&mut self) -> Option<Point<T>> {
+        self.path.pop_back()
+    }
+    fn path(&self) -> &S {
+        &self.path
+    }
+    fn reset(&mut self) {
+        self.path.clear();
+
+        self.current_dist = T::zero();
+    }
+    // generate arrivals until total distance is reached.
+    fn generate(&mut self) {
+        while self.current_dist < self.total_dist {
+            self.generate_single();
+        }
+    }
+    // generate single arrival.
+    fn generate_single(&mut self) {
+        let gap = exponential_gap(&mut self.rng, self.average_dist);
+
+        self.current_dist = self.current_dist + gap;
+
+        // next arrival lies beyond total distance -> no further events in this interval.
+        if self.current_dist > self.total_dist {
+            self.current_dist = self.total_dist;
+            return;
+        }
+
+        // y: cumulative count of arrivals so far.
+        let count = self.path.tail().unwrap_or(&self.initial).y + T::one();
+
+        self.push_back( Point { x: self.initial.x + self.current_dist, y: count } );
+    }
+}
+
+impl<T: Float> Poisson<T> {
+    pub fn new(initial: Point<T>, average_dist: T, total_dist: T) -> Self {
+        Self::with_rng(initial, average_dist, total_dist, default_rng())
+    }
+    // same seed and parameters -> identical path.
+    pub fn with_seed(initial: Point<T>, average_dist: T, total_dist: T, seed: u64) -> Self {
+        Self::with_rng(initial, average_dist, total_dist, seeded_rng(seed))
+    }
+}
+
+impl<T: Float, R: Rng> Poisson<T, R> {
+    pub fn with_rng(initial: Point<T>, average_dist: T, total_dist: T, rng: R) -> Self {
+        assert_average_dist(average_dist);
+
+        Self {
+            path: Path::new(),
+
+            // model parameters
+            initial,
+            current_dist: T::zero(),
+            average_dist,
+            total_dist,
+
+            rng,
+        }
+    }
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> Poisson<T, R, S> {
+    // swap the storage backend. points generated so far are dropped, generation starts over from the initial point.
+    pub fn with_storage<U: PathStorage<T>>(self, storage: U) -> Poisson<T, R, U> {
+        Poisson {
+            path: storage,
+
+            initial: self.initial,
+            current_dist: T::zero(),
+            average_dist: self.average_dist,
+            total_dist: self.total_dist,
+            rng: self.rng,
+        }
+    }
+    pub fn set_average_dist(&mut self, average_dist: T) {
+        assert_average_dist(average_dist);
+
+        self.average_dist = average_dist;
+    }
+    pub fn set_total_dist(&mut self, total_dist: T) {
+        self.total_dist = total_dist;
+    }
+}
+
+// --- theory :
+// arrivals as in Poisson, but every arrival adds a random jump instead of 1.
+// -> total claim amount of an insurer, accumulated damage of random events, ...
+
+// --- here:
+// jump sizes come from any rand_distr distribution over T (Normal, Exp, ...), Constant or FromFn.
 #[derive(Debug, Clone)]
+pub struct CompoundPoisson<D, T: Float = f64, R: Rng = DefaultRng, S: PathStorage<T> = Path<T>>
+{
+    path: S, // generated points
+
+    initial: Point<T>, // starting point
+
+    current_dist: T, // current time ( -> as total distance )
+    average_dist: T, // average time it takes for event to occur. ( -> as average distance )
+    total_dist: T, // total time used for generation. ( -> as total distance.)
+
+    jumps: D, // distribution of the jump added at each arrival
+
+    rng: R, // source of randomness. seeded -> reproducible path.
+}
+
+impl<D: Distribution<T>, T: Float, R: Rng, S: PathStorage<T>> IntoIterator for CompoundPoisson<D, T, R, S> {
+    type Item = Point<T>;
+    type IntoIter = ProcessIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.path.into_iter()
+    }
+}
+
+impl<D: Distribution<T>, T: Float, R: Rng, S: PathStorage<T>> Process for CompoundPoisson<D, T, R, S> {
+    type Scalar = T;
+    type Storage = S;
+
+    #[inline]
+    fn push_back(&mut self, point: Point<T>) -> usize {
+        self.path.push_back(point)
+    }
+    #[inline]
+    fn pop_back(&mut self) -> Option<Point<T>> {
+        self.path.pop_back()
+    }
+    fn path(&self) -> &S {
+        &self.path
+    }
+    fn reset(&mut self) {
+        self.path.clear();
+
+        self.current_dist = T::zero();
+    }
+    // generate arrivals until total distance is reached.
+    fn generate(&mut self) {
+        while self.current_dist < self.total_dist {
+            self.generate_single();
+        }
+    }
+    // generate single arrival.
+    fn generate_single(&mut self) {
+        let gap = exponential_gap(&mut self.rng, self.average_dist);
+
+        self.current_dist = self.current_dist + gap;
+
+        // next arrival lies beyond total distance -> no further events in this interval.
+        if self.current_dist > self.total_dist {
+            self.current_dist = self.total_dist;
+            return;
+        }
+
+        // y: sum of all jumps so far.
+        let jump = self.jumps.sample(&mut self.rng);
+        let sum = self.path.tail().unwrap_or(&self.initial).y + jump;
+
+        self.push_back( Point { x: self.initial.x + self.current_dist, y: sum } );
+    }
+}
+
+impl<D: Distribution<T>, T: Float> CompoundPoisson<D, T> {
+    pub fn new(initial: Point<T>, average_dist: T, total_dist: T, jumps: D) -> Self {
+        Self::with_rng(initial, average_dist, total_dist, jumps, default_rng())
+    }
+    // same seed and parameters -> identical path.
+    pub fn with_seed(initial: Point<T>, average_dist: T, total_dist: T, jumps: D, seed: u64) -> Self {
+        Self::with_rng(initial, average_dist, total_dist, jumps, seeded_rng(seed))
+    }
+}
+
+impl<D: Distribution<T>, T: Float, R: Rng> CompoundPoisson<D, T, R> {
+    pub fn with_rng(initial: Point<T>, average_dist: T, total_dist: T, jumps: D, rng: R) -> Self {
+        assert_average_dist(average_dist);
+
+        Self {
+            path: Path::new(),
+
+            // model parameters
+            initial,
+            current_dist: T::zero(),
+            average_dist,
+            total_dist,
 
-pub struct Poisson
+            jumps,
+
+            rng,
+        }
+    }
+}
+
+impl<D: Distribution<T>, T: Float, R: Rng, S: PathStorage<T>> CompoundPoisson<D, T, R, S> {
+    // swap the storage backend. points generated so far are dropped, generation starts over from the initial point.
+    pub fn with_storage<U: PathStorage<T>>(self, storage: U) -> CompoundPoisson<D, T, R, U> {
+        CompoundPoisson {
+            path: storage,
+
+            initial: self.initial,
+            current_dist: T::zero(),
+            average_dist: self.average_dist,
+            total_dist: self.total_dist,
+            jumps: self.jumps,
+            rng: self.rng,
+        }
+    }
+    pub fn set_average_dist(&mut self, average_dist: T) {
+        assert_average_dist(average_dist);
+
+        self.average_dist = average_dist;
+    }
+    pub fn set_total_dist(&mut self, total_dist: T) {
+        self.total_dist = total_dist;
+    }
+    pub fn set_jumps(&mut self, jumps: D) {
+        self.jumps = jumps;
+    }
+}
+
+// rate of arrivals per unit of x, varying along the path.
+pub trait Intensity<T> {
+    fn rate(&self, t: T) -> T; // lambda(t). t: distance from the initial point.
+
+    fn upper_bound(&self) -> T; // lambda_max >= lambda(t) for every t. used for thinning.
+}
+
+// intensity given by a closure together with an upper bound of its values.
+#[derive(Debug, Clone, Copy)]
+pub struct IntensityFn<F, T> {
+    rate: F,
+    upper_bound: T,
+}
+
+impl<F: Fn(T) -> T, T: Float> IntensityFn<F, T> {
+    // upper_bound must not be exceeded by rate(t) on the generated interval, otherwise arrivals are missed.
+    // panics unless upper_bound is finite and not negative: thinning could never finish.
+    pub fn new(rate: F, upper_bound: T) -> Self {
+        assert!(upper_bound.is_finite() && upper_bound >= T::zero(), "upper bound must be finite and not negative");
+
+        Self {
+            rate,
+            upper_bound,
+        }
+    }
+}
+
+impl<F: Fn(T) -> T, T: Float> Intensity<T> for IntensityFn<F, T> {
+    fn rate(&self, t: T) -> T {
+        (self.rate)(t)
+    }
+    fn upper_bound(&self) -> T {
+        self.upper_bound
+    }
+}
+
+// intensity from a table of (t, rate) nodes, linearly interpolated in between.
+// constant before the first and after the last node. e.g. time-of-day patterns, ramps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PiecewiseIntensity<T: Float = f64> {
+    nodes: Vec<(T, T)>,
+}
+
+impl<T: Float> PiecewiseIntensity<T> {
+    pub fn new(mut nodes: Vec<(T, T)>) -> Self {
+        assert!(!nodes.is_empty(), "piecewise intensity needs at least one node");
+        assert!(nodes.iter().all(|&(_, rate)| rate >= T::zero()), "intensity must not be negative");
+
+        nodes.sort_by(|a, b| a.0.partial_cmp(&b.0).expect("node positions must not be NaN"));
+
+        Self {
+            nodes,
+        }
+    }
+}
+
+impl<T: Float> Intensity<T> for PiecewiseIntensity<T> {
+    fn rate(&self, t: T) -> T {
+        // index of the first node after t.
+        let i = self.nodes.partition_point(|&(x, _)| x <= t);
+
+        if i == 0 {
+            return self.nodes[0].1;
+        }
+        if i == self.nodes.len() {
+            return self.nodes[i - 1].1;
+        }
+        let (x0, r0) = self.nodes[i - 1];
+        let (x1, r1) = self.nodes[i];
+
+        r0 + (r1 - r0) * (t - x0) / (x1 - x0)
+    }
+    fn upper_bound(&self) -> T {
+        self.nodes.iter().fold(T::zero(), |max, &(_, rate)| max.max(rate))
+    }
+}
+
+// --- theory :
+// poisson arrivals whose rate lambda(t) changes along the x axis.
+
+// --- here:
+// thinning (lewis-shedler): candidates arrive at the constant rate lambda_max,
+// a candidate at t is kept with probability lambda(t) / lambda_max.
+#[derive(Debug, Clone)]
+pub struct NonHomogeneousPoisson<I, T: Float = f64, R: Rng = DefaultRng, S: PathStorage<T> = Path<T>>
 {
-    head: Option<*mut Node>,
-    tail: Option<*mut Node>,
+    path: S, // generated points
 
-    len: usize,
+    initial: Point<T>, // starting point
 
-    initial: Point, // starting point
+    current_dist: T, // current time ( -> as total distance )
+    total_dist: T, // total time used for generation. ( -> as total distance.)
 
-    current_dist: f32, // current time ( -> as total distance )
-    average_dist: f32, // average time it takes for event to occur. ( -> as average distance )
-    total_dist: f32, // total time used for generation. ( -> as total distance.)
+    intensity: I, // arrival rate as a function of current_dist
+
+    rng: R, // source of randomness. seeded -> reproducible path.
 }
 
-impl IntoIterator for Poisson {
-    type Item = Point;
-    type IntoIter = ProcessIter;
+impl<I: Intensity<T>, T: Float, R: Rng, S: PathStorage<T>> IntoIterator for NonHomogeneousPoisson<I, T, R, S> {
+    type Item = Point<T>;
+    type IntoIter = ProcessIter<T>;
 
     fn into_iter(self) -> Self::IntoIter {
-        ProcessIter {
-            head: self.head,
-            tail: self.tail,
-            len: (self.total_dist / self.average_dist) as usize,
+        self.path.into_iter()
+    }
+}
+
+impl<I: Intensity<T>, T: Float, R: Rng, S: PathStorage<T>> Process for NonHomogeneousPoisson<I, T, R, S> {
+    type Scalar = T;
+    type Storage = S;
+
+    #[inline]
+    fn push_back(&mut self, point: Point<T>) -> usize {
+        self.path.push_back(point)
+    }
+    #[inline]
+    fn pop_back(&mut self) -> Option<Point<T>> {
+        self.path.pop_back()
+    }
+    fn path(&self) -> &S {
+        &self.path
+    }
+    fn reset(&mut self) {
+        self.path.clear();
+
+        self.current_dist = T::zero();
+    }
+    // generate arrivals until total distance is reached.
+    fn generate(&mut self) {
+        while self.current_dist < self.total_dist {
+            self.generate_single();
         }
     }
-}
\ No newline at end of file
+    // generate single arrival. rejected candidates are skipped.
+    fn generate_single(&mut self) {
+        let upper_bound = self.intensity.upper_bound();
+
+        // no arrivals possible.
+        if upper_bound <= T::zero() {
+            self.current_dist = self.total_dist;
+            return;
+        }
+
+        loop {
+            let gap = exponential_gap(&mut self.rng, T::one() / upper_bound);
+
+            self.current_dist = self.current_dist + gap;
+
+            // next candidate lies beyond total distance -> no further events in this interval.
+            if self.current_dist > self.total_dist {
+                self.current_dist = self.total_dist;
+                return;
+            }
+
+            let rate = self.intensity.rate(self.current_dist);
+            debug_assert!(rate <= upper_bound, "intensity exceeds its upper bound");
+
+            let accept = rate / upper_bound;
+
+            if uniform::<T, R>(&mut self.rng) < accept {
+                break;
+            }
+        }
+
+        // y: cumulative count of arrivals so far.
+        let count = self.path.tail().unwrap_or(&self.initial).y + T::one();
+
+        self.push_back( Point { x: self.initial.x + self.current_dist, y: count } );
+    }
+}
+
+impl<I: Intensity<T>, T: Float> NonHomogeneousPoisson<I, T> {
+    pub fn new(initial: Point<T>, total_dist: T, intensity: I) -> Self {
+        Self::with_rng(initial, total_dist, intensity, default_rng())
+    }
+    // same seed and parameters -> identical path.
+    pub fn with_seed(initial: Point<T>, total_dist: T, intensity: I, seed: u64) -> Self {
+        Self::with_rng(initial, total_dist, intensity, seeded_rng(seed))
+    }
+}
+
+impl<I: Intensity<T>, T: Float, R: Rng> NonHomogeneousPoisson<I, T, R> {
+    pub fn with_rng(initial: Point<T>, total_dist: T, intensity: I, rng: R) -> Self {
+        Self {
+            path: Path::new(),
+
+            // model parameters
+            initial,
+            current_dist: T::zero(),
+            total_dist,
+
+            intensity,
+
+            rng,
+        }
+    }
+}
+
+impl<I: Intensity<T>, T: Float, R: Rng, S: PathStorage<T>> NonHomogeneousPoisson<I, T, R, S> {
+    // swap the storage backend. points generated so far are dropped, generation starts over from the initial point.
+    pub fn with_storage<U: PathStorage<T>>(self, storage: U) -> NonHomogeneousPoisson<I, T, R, U> {
+        NonHomogeneousPoisson {
+            path: storage,
+
+            initial: self.initial,
+            current_dist: T::zero(),
+            total_dist: self.total_dist,
+            intensity: self.intensity,
+            rng: self.rng,
+        }
+    }
+    pub fn intensity(&self) -> &I {
+        &self.intensity
+    }
+    pub fn set_total_dist(&mut self, total_dist: T) {
+        self.total_dist = total_dist;
+    }
+    pub fn set_intensity(&mut self, intensity: I) {
+        self.intensity = intensity;
+    }
+}
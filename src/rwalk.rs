@@ -1,153 +1,606 @@
+// --- theory :
+// lattice walk: at every step the value moves up, down or stays.
+// probabilities of the three moves and the size of each move are configurable:
+// - simple symmetric walk: up = down = 0.5, constant step.
+// - biased walk: up != down.   lazy walk: up + down < 1, the rest is the probability to stay.
+// - continuous steps: up = 1, down = 0, with signed step sizes, e.g. rand_distr::Normal.
+// - levy flight: heavy tailed step sizes, e.g. rand_distr::Pareto, with a random direction.
+
+// barriers: reflecting barriers mirror the value back inside,
+// absorbing barriers stop the walk at the barrier for the remaining steps.
+
+use num_traits::Float;
 use rand::Rng;
+use rand_distr::Distribution;
 
 use crate::base::*;
 
-#[derive(Debug)]
-pub struct RandomWalk1D {
-    head: Option<*mut Node>,
-    tail: Option<*mut Node>,
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Barrier<T: Float = f64> {
+    Reflecting(T), // values beyond the level are mirrored at it
+    Absorbing(T), // the walk stays at the level once it is reached
+}
+
+impl<T: Float> Barrier<T> {
+    pub fn level(&self) -> T {
+        match *self {
+            Barrier::Reflecting(level) | Barrier::Absorbing(level) => level,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RandomWalk1D<T: Float = f64, D: Distribution<T> = Constant<T>, R: Rng = DefaultRng, S: PathStorage<T> = Path<T>> {
+    path: S, // generated points
+
+    initial: Point<T>,
 
-    len: usize,
+    step: Point<T>, // distance per loop/step ( x and y )
+    step_size: D, // size of a move. defaults to the constant step.y
+    up: T, // probability to move up
+    down: T, // probability to move down. stay: 1 - up - down
 
-    initial: Point,
+    lower: Option<Barrier<T>>,
+    upper: Option<Barrier<T>>,
+    absorbed: bool, // an absorbing barrier has been reached
 
-    step: Point, // distance per loop/step ( x and y )
-    distance: f32, // total distance: multiple of step.x
+    remaining: u32, // steps left to generate. counted, so float rounding cannot add a step.
+    loops: u32, // steps per generation. reset restores remaining to it.
+
+    rng: R, // source of randomness. seeded -> reproducible path.
 }
 
-impl IntoIterator for RandomWalk1D {
-    type Item = Point;
-    type IntoIter = ProcessIter;
+impl<T: Float, D: Distribution<T>, R: Rng, S: PathStorage<T>> IntoIterator for RandomWalk1D<T, D, R, S> {
+    type Item = Point<T>;
+    type IntoIter = ProcessIter<T>;
 
     fn into_iter(self) -> Self::IntoIter {
-        ProcessIter {
-            head: self.head,
-            tail: self.tail,
-            len: (self.distance / self.step.x) as usize,
-        }
+        self.path.into_iter()
     }
 }
 
-impl Process for RandomWalk1D {
+impl<T: Float, D: Distribution<T>, R: Rng, S: PathStorage<T>> Process for RandomWalk1D<T, D, R, S> {
+    type Scalar = T;
+    type Storage = S;
+
     #[inline]
-    fn push_back(&mut self, mut boxed: Box<Node>) -> usize {
-    
-        boxed.next = None;
-        boxed.prev = self.tail; // assign current tail node of container to prev pointer of new node. 
-                                // -> (the container's last element)
-        unsafe {
-            let node = Box::leak(boxed); // leak the box.
-
-            match self.tail {
-                // no elements in container. create head node.
-                None => self.head = Some(node),
-                // elements exist. assign next pointer of tail to new node.
-                Some(tail) => (*tail).next = Some(node),
+    fn push_back(&mut self, point: Point<T>) -> usize {
+        self.path.push_back(point)
+    }
+    #[inline]
+    fn pop_back(&mut self) -> Option<Point<T>> {
+        let point = self.path.pop_back();
+
+        // still absorbed if the new tail sits on an absorbing barrier.
+        self.absorbed = self.path.tail().is_some_and(|tail| self.is_at_absorbing_barrier(tail.y));
+        point
+    }
+    fn path(&self) -> &S {
+        &self.path
+    }
+    fn reset(&mut self) {
+        self.path.clear();
+        self.absorbed = false;
+
+        self.remaining = self.loops;
+    }
+    fn generate(&mut self) {
+        while self.remaining > 0 {
+            self.generate_single();
+        }
+    }
+    // generate single value
+    fn generate_single(&mut self) {
+        let cur = *self.path.tail().unwrap_or(&self.initial);
+
+        let mut point: Point<T> = Point {x: cur.x + self.step.x, y: cur.y };
+
+        if !self.absorbed {
+            let u: T = uniform(&mut self.rng);
+
+            if u < self.up {
+                // move up
+                point.y = cur.y + self.step_size.sample(&mut self.rng);
+            } else if u < self.up + self.down {
+                // move down
+                point.y = cur.y - self.step_size.sample(&mut self.rng);
             }
+            // otherwise: stay
 
-            // independently of whether tail exists, assign current node to tail.
-            self.tail = Some(node);
+            point.y = self.apply_barriers(point.y);
         }
-        self.len += 1;
-        self.len
+
+        self.push_back(point);
+
+        self.remaining = self.remaining.saturating_sub(1);
     }
-    #[inline]
-    fn pop_back(&mut self) -> Option<Box<Node>> {
-        let result = match self.tail {
-            None => {
-                // hint: not necessary to Box::from_raw(self.head), since the memory is automatically boxed 
-                // when the first self.tail is popped (below)
-                self.head = None;
-                None
+}
+
+impl<T: Float> RandomWalk1D<T> {
+    pub fn new(initial: Point<T>, step: Point<T>, steps: u32) -> Self {
+        Self::with_rng(initial, step, steps, default_rng())
+    }
+    // same seed and parameters -> identical path.
+    pub fn with_seed(initial: Point<T>, step: Point<T>, steps: u32, seed: u64) -> Self {
+        Self::with_rng(initial, step, steps, seeded_rng(seed))
+    }
+}
+
+impl<T: Float, R: Rng> RandomWalk1D<T, Constant<T>, R> {
+    // simple symmetric walk: up or down by step.y with probability 1/2 each.
+    pub fn with_rng(initial: Point<T>, step: Point<T>, steps: u32, rng: R) -> Self {
+        let half: T = scalar(0.5);
+
+        Self {
+            path: Path::with_capacity(steps as usize),
+
+            // model parameters
+            initial,
+            step,
+            step_size: Constant(step.y),
+            up: half,
+            down: half,
+
+            lower: None,
+            upper: None,
+            absorbed: false,
+
+            remaining: steps,
+            loops: steps,
+
+            rng,
+        }
+    }
+}
+
+impl<T: Float, D: Distribution<T>, R: Rng, S: PathStorage<T>> RandomWalk1D<T, D, R, S> {
+    // swap the storage backend. points generated so far are dropped, generation starts over from the initial point.
+    pub fn with_storage<U: PathStorage<T>>(self, storage: U) -> RandomWalk1D<T, D, R, U> {
+        RandomWalk1D {
+            path: storage,
+
+            initial: self.initial,
+            step: self.step,
+            step_size: self.step_size,
+            up: self.up,
+            down: self.down,
+            lower: self.lower,
+            upper: self.upper,
+            absorbed: false,
+            remaining: self.loops,
+            loops: self.loops,
+            rng: self.rng,
+        }
+    }
+    // draw the size of each move from a distribution instead of the constant step.y.
+    pub fn with_step_distribution<E: Distribution<T>>(self, step_size: E) -> RandomWalk1D<T, E, R, S> {
+        RandomWalk1D {
+            path: self.path,
+
+            initial: self.initial,
+            step: self.step,
+            step_size,
+            up: self.up,
+            down: self.down,
+            lower: self.lower,
+            upper: self.upper,
+            absorbed: self.absorbed,
+            remaining: self.remaining,
+            loops: self.loops,
+            rng: self.rng,
+        }
+    }
+    // probabilities to move up and down. the walk stays with probability 1 - up - down.
+    pub fn with_probabilities(mut self, up: T, down: T) -> Self {
+        self.set_probabilities(up, down);
+        self
+    }
+    pub fn with_lower_barrier(mut self, barrier: Barrier<T>) -> Self {
+        self.lower = Some(barrier);
+        self
+    }
+    pub fn with_upper_barrier(mut self, barrier: Barrier<T>) -> Self {
+        self.upper = Some(barrier);
+        self
+    }
+    pub fn set_probabilities(&mut self, up: T, down: T) {
+        assert!(up >= T::zero() && down >= T::zero() && up + down <= T::one(),
+            "probabilities must be non-negative and sum to at most 1");
+
+        self.up = up;
+        self.down = down;
+    }
+    // true once an absorbing barrier has been reached.
+    pub fn is_absorbed(&self) -> bool {
+        self.absorbed
+    }
+    // value lies on or beyond an absorbing barrier.
+    fn is_at_absorbing_barrier(&self, value: T) -> bool {
+        matches!(self.lower, Some(Barrier::Absorbing(level)) if value <= level)
+            || matches!(self.upper, Some(Barrier::Absorbing(level)) if value >= level)
+    }
+    // keep a new value inside the barriers.
+    fn apply_barriers(&mut self, mut value: T) -> T {
+        let two: T = scalar(2);
+
+        if let Some(barrier) = self.lower {
+            let level = barrier.level();
+
+            if value <= level {
+                match barrier {
+                    Barrier::Reflecting(_) => value = two * level - value,
+                    Barrier::Absorbing(_) => {
+                        self.absorbed = true;
+                        return level;
+                    }
+                }
             }
-            Some(tail) => {
-                unsafe {
-                    let mut boxed = Box::from_raw(tail);
-                    boxed.next = None;
-                    if let Some(prev) = boxed.prev {
-                        self.tail = Some(prev);
+        }
+        if let Some(barrier) = self.upper {
+            let level = barrier.level();
+
+            if value >= level {
+                match barrier {
+                    Barrier::Reflecting(_) => value = two * level - value,
+                    Barrier::Absorbing(_) => {
+                        self.absorbed = true;
+                        return level;
                     }
-                    Some(boxed)
                 }
             }
-        };
+        }
+        // a step larger than the gap between two reflecting barriers can overshoot again.
+        if let (Some(lower), Some(upper)) = (self.lower, self.upper) {
+            value = value.max(lower.level()).min(upper.level());
+        }
+        // a reflection can carry the value onto the absorbing barrier at the other side.
+        if self.is_at_absorbing_barrier(value) {
+            self.absorbed = true;
+        }
+        value
+    }
+}
+
+// --- spatial walks :
+// the points are positions in the plane (or in n dimensions), not values over a time axis.
+// -> wandering characters, particles, procedural paths in 2d games and graphics.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoundaryMode {
+    Wrap, // leaving one side re-enters from the opposite side (torus)
+    Reflect, // positions beyond a side are mirrored back inside
+}
 
-        result
+// axis-aligned box a walk is confined to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds<T: Float, const N: usize> {
+    pub min: [T; N],
+    pub max: [T; N],
+    pub mode: BoundaryMode,
+}
+
+impl<T: Float, const N: usize> Bounds<T, N> {
+    pub fn new(min: [T; N], max: [T; N], mode: BoundaryMode) -> Self {
+        assert!(min.iter().zip(max.iter()).all(|(lo, hi)| lo < hi), "bounds must have min < max on every axis");
+
+        Self {
+            min,
+            max,
+            mode,
+        }
     }
-    fn reset(&mut self) {
-        // if stopped, either end reached (reset complete), or linked list is broken.
-        unsafe {
-            // tail: currently the last node.
-            while let Some(tail) = self.tail {
-
-                // box the value. making it memory safe.
-                let boxed = Box::from_raw(tail);
-
-                boxed.prev.map( |node| {
-                    (*node).next = None;
-                });
-                self.tail = boxed.prev;
-            }
-            self.head = None;
+    // move a position back inside the box.
+    pub fn confine(&self, mut position: [T; N]) -> [T; N] {
+        for (axis, value) in position.iter_mut().enumerate() {
+            let (lo, hi) = (self.min[axis], self.max[axis]);
+            let width = hi - lo;
+
+            *value = match self.mode {
+                BoundaryMode::Wrap => lo + modulo(*value - lo, width),
+                BoundaryMode::Reflect => {
+                    // triangle wave with period 2 * width: handles steps longer than the box.
+                    let two: T = scalar(2);
+                    let offset = modulo(*value - lo, two * width);
+
+                    if offset > width { lo + two * width - offset } else { lo + offset }
+                }
+            };
+        }
+        position
+    }
+}
+
+// remainder in [0, m)
+fn modulo<T: Float>(value: T, m: T) -> T {
+    let r = value % m;
+
+    if r >= T::zero() {
+        return r;
+    }
+    // a tiny negative r rounds up to m: that is the lower end again.
+    let wrapped = r + m;
+
+    if wrapped < m { wrapped } else { T::zero() }
+}
+
+// uniformly distributed direction of length `length` in N dimensions.
+fn random_direction<T: Float, R: Rng, const N: usize>(rng: &mut R, length: T) -> [T; N] {
+    loop {
+        let mut direction = [T::zero(); N];
+        for value in direction.iter_mut() {
+            *value = standard_normal(rng);
+        }
+        let norm = direction.iter().fold(T::zero(), |sum, &v| sum + v * v).sqrt();
+
+        // a zero vector has no direction. practically never happens, draw again.
+        if norm > T::zero() {
+            return direction.map(|v| v / norm * length);
         }
     }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighbourhood {
+    Four, // up, down, left, right
+    Eight, // the four above plus diagonals. diagonal moves change both coordinates by the step.
+    ContinuousAngle, // any direction, uniform angle
+}
+
+#[derive(Debug, Clone)]
+pub struct RandomWalk2D<T: Float = f64, R: Rng = DefaultRng, S: PathStorage<T> = Path<T>> {
+    path: S, // generated positions
+
+    initial: Point<T>, // starting position
+
+    step: T, // length of an axis move
+    neighbourhood: Neighbourhood,
+    bounds: Option<Bounds<T, 2>>,
+
+    remaining: u32, // steps left to generate. counted, so float rounding cannot add a step.
+    loops: u32, // steps per generation. reset restores remaining to it.
+
+    rng: R, // source of randomness. seeded -> reproducible path.
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> IntoIterator for RandomWalk2D<T, R, S> {
+    type Item = Point<T>;
+    type IntoIter = ProcessIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.path.into_iter()
+    }
+}
+
+impl<T: Float, R: Rng, S: PathStorage<T>> Process for RandomWalk2D<T, R, S> {
+    type Scalar = T;
+    type Storage = S;
+
+    #[inline]
+    fn push_back(&mut self, point: Point<T>) -> usize {
+        self.path.push_back(point)
+    }
+    #[inline]
+    fn pop_back(&mut self) -> Option<Point<T>> {
+        self.path.pop_back()
+    }
+    fn path(&self) -> &S {
+        &self.path
+    }
+    fn reset(&mut self) {
+        self.path.clear();
+
+        self.remaining = self.loops;
+    }
     fn generate(&mut self) {
-        while self.distance > 0.0 {
+        while self.remaining > 0 {
             self.generate_single();
         }
     }
-    // generate single value
+    // generate single position
     fn generate_single(&mut self) {
-        let mut point: Point = Point {x: 0.0, y: 0.0 };
-
-        if let Some(tail) = self.tail {
-            unsafe {
-                let cur = &(*tail).current;
-                
-                point.x = cur.x + self.step.x;
+        let cur = *self.path.tail().unwrap_or(&self.initial);
 
-                if rand::thread_rng().gen_ratio(1, 2) {
-                    // upper 50% : move up
-                    point.y = cur.y + self.step.y;
-                } else {
-                    // lower 50% : move down
-                    point.y = cur.y - self.step.y;
+        let (dx, dy) = match self.neighbourhood {
+            Neighbourhood::Four => {
+                match self.rng.gen_range(0..4) {
+                    0 => (self.step, T::zero()),
+                    1 => (-self.step, T::zero()),
+                    2 => (T::zero(), self.step),
+                    _ => (T::zero(), -self.step),
+                }
+            }
+            Neighbourhood::Eight => {
+                // 3 x 3 neighbourhood without the centre.
+                let mut cell = self.rng.gen_range(0..8);
+                if cell >= 4 {
+                    cell += 1;
                 }
-                
-                let boxed = Box::new( Node::new(point) );        
-                self.push_back(boxed);
+                let offset = |index: i32| scalar::<T, _>(index - 1) * self.step;
+
+                (offset(cell % 3), offset(cell / 3))
             }
-        } else {
-            point.x = self.initial.x + self.step.x;
-
-            if rand::thread_rng().gen_ratio(1, 2) {
-                // upper 50% : move up 
-                point.y = self.initial.y + self.step.y;
-            } else {
-                // lower 50% : move down
-                point.y = self.initial.y - self.step.y;
+            Neighbourhood::ContinuousAngle => {
+                let [dx, dy] = random_direction(&mut self.rng, self.step);
+                (dx, dy)
             }
+        };
 
-            let boxed = Box::new( Node::new(point) ); 
-            self.push_back(boxed);
+        let mut position = [cur.x + dx, cur.y + dy];
+        if let Some(bounds) = &self.bounds {
+            position = bounds.confine(position);
         }
 
-        self.distance -= self.step.x;
+        self.push_back( Point { x: position[0], y: position[1] } );
+
+        self.remaining = self.remaining.saturating_sub(1);
     }
 }
 
-impl RandomWalk1D {
-    pub fn new(initial: Point, step: Point, steps: u32) -> Self {
+impl<T: Float> RandomWalk2D<T> {
+    pub fn new(initial: Point<T>, step: T, steps: u32, neighbourhood: Neighbourhood) -> Self {
+        Self::with_rng(initial, step, steps, neighbourhood, default_rng())
+    }
+    // same seed and parameters -> identical path.
+    pub fn with_seed(initial: Point<T>, step: T, steps: u32, neighbourhood: Neighbourhood, seed: u64) -> Self {
+        Self::with_rng(initial, step, steps, neighbourhood, seeded_rng(seed))
+    }
+}
+
+impl<T: Float, R: Rng> RandomWalk2D<T, R> {
+    pub fn with_rng(initial: Point<T>, step: T, steps: u32, neighbourhood: Neighbourhood, rng: R) -> Self {
         Self {
-            head: None,
-            tail: None,
+            path: Path::with_capacity(steps as usize),
+
+            // model parameters
+            initial,
+            step,
+            neighbourhood,
+            bounds: None,
+
+            remaining: steps,
+            loops: steps,
+
+            rng,
+        }
+    }
+}
 
-            len: 0,
+impl<T: Float, R: Rng, S: PathStorage<T>> RandomWalk2D<T, R, S> {
+    // swap the storage backend. points generated so far are dropped, generation starts over from the initial point.
+    pub fn with_storage<U: PathStorage<T>>(self, storage: U) -> RandomWalk2D<T, R, U> {
+        RandomWalk2D {
+            path: storage,
+
+            initial: self.initial,
+            step: self.step,
+            neighbourhood: self.neighbourhood,
+            bounds: self.bounds,
+            remaining: self.loops,
+            loops: self.loops,
+            rng: self.rng,
+        }
+    }
+    // confine the walk to a box. min/max: [x, y]
+    pub fn with_bounds(mut self, bounds: Bounds<T, 2>) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+    pub fn set_step(&mut self, step: T) {
+        self.step = step;
+    }
+    pub fn set_neighbourhood(&mut self, neighbourhood: Neighbourhood) {
+        self.neighbourhood = neighbourhood;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveND {
+    Lattice, // one axis per step, forwards or backwards: 2 * N neighbours
+    Continuous, // any direction, uniform on the sphere
+}
+
+// walk in N dimensions. positions are [T; N] arrays, so this walk keeps its own
+// position list instead of a point path, with the same generate/reset interface.
+#[derive(Debug, Clone)]
+pub struct RandomWalkND<T: Float, const N: usize, R: Rng = DefaultRng> {
+    positions: Vec<[T; N]>, // generated positions
+
+    initial: [T; N], // starting position
+
+    step: T, // length of a move
+    moves: MoveND,
+    bounds: Option<Bounds<T, N>>,
+
+    remaining: u32, // steps left to generate. counted, so float rounding cannot add a step.
+    loops: u32, // steps per generation. reset restores remaining to it.
+
+    rng: R, // source of randomness. seeded -> reproducible path.
+}
+
+impl<T: Float, const N: usize> RandomWalkND<T, N> {
+    pub fn new(initial: [T; N], step: T, steps: u32, moves: MoveND) -> Self {
+        Self::with_rng(initial, step, steps, moves, default_rng())
+    }
+    // same seed and parameters -> identical path.
+    pub fn with_seed(initial: [T; N], step: T, steps: u32, moves: MoveND, seed: u64) -> Self {
+        Self::with_rng(initial, step, steps, moves, seeded_rng(seed))
+    }
+}
+
+impl<T: Float, const N: usize, R: Rng> RandomWalkND<T, N, R> {
+    pub fn with_rng(initial: [T; N], step: T, steps: u32, moves: MoveND, rng: R) -> Self {
+        assert!(N > 0, "a walk needs at least one dimension");
+
+        Self {
+            positions: Vec::with_capacity(steps as usize),
 
             // model parameters
-            initial: initial,
-            step: step,
-            distance: steps as f32 * step.x,
+            initial,
+            step,
+            moves,
+            bounds: None,
+
+            remaining: steps,
+            loops: steps,
+
+            rng,
         }
     }
-}
\ No newline at end of file
+    // confine the walk to a box.
+    pub fn with_bounds(mut self, bounds: Bounds<T, N>) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+    // generate all remaining steps
+    pub fn generate(&mut self) {
+        while self.remaining > 0 {
+            self.generate_single();
+        }
+    }
+    // generate single position
+    pub fn generate_single(&mut self) {
+        let mut position = *self.positions.last().unwrap_or(&self.initial);
+
+        match self.moves {
+            MoveND::Lattice => {
+                let axis = self.rng.gen_range(0..N);
+
+                if self.rng.gen_bool(0.5) {
+                    position[axis] = position[axis] + self.step;
+                } else {
+                    position[axis] = position[axis] - self.step;
+                }
+            }
+            MoveND::Continuous => {
+                let direction: [T; N] = random_direction(&mut self.rng, self.step);
+
+                for (value, delta) in position.iter_mut().zip(direction.iter()) {
+                    *value = *value + *delta;
+                }
+            }
+        }
+
+        if let Some(bounds) = &self.bounds {
+            position = bounds.confine(position);
+        }
+        self.positions.push(position);
+
+        self.remaining = self.remaining.saturating_sub(1);
+    }
+    // remove all positions
+    pub fn reset(&mut self) {
+        self.positions.clear();
+
+        self.remaining = self.loops;
+    }
+    pub fn pop_back(&mut self) -> Option<[T; N]> {
+        self.positions.pop()
+    }
+    // generated positions, in order of generation.
+    pub fn positions(&self) -> &[[T; N]] {
+        &self.positions
+    }
+    pub fn set_step(&mut self, step: T) {
+        self.step = step;
+    }
+}
@@ -0,0 +1,518 @@
+// --- theory :
+// ito stochastic differential equation  dX = a(t, X) dt + b(t, X) dW.
+// every model here is a special case, e.g. gbm: a = mu * x, b = sigma * x.
+
+// --- here:
+// one struct for user-defined drift a and diffusion b, integrated with a selectable scheme.
+// x of a point is the time t, y the value X(t).
+
+use num_traits::Float;
+use rand::Rng;
+
+use crate::base::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SdeScheme {
+    #[default]
+    EulerMaruyama, // X + a dt + b dW. strong order 0.5.
+    Milstein, // adds 0.5 * b * b' * (dW^2 - dt). strong order 1.0, needs b' = db/dx.
+    RungeKutta, // platen's derivative-free scheme, b' replaced by a difference quotient. strong order 1.0.
+}
+
+// relative size of the difference used for b' when no derivative is supplied.
+const DERIVATIVE_STEP: f64 = 1e-6;
+
+// a(t, x), b(t, x), optional b'(t, x) and the scheme combining them.
+// shared by the fixed-step and the adaptive integrator.
+#[derive(Debug, Clone)]
+struct Dynamics<A, B, D> {
+    drift: A, // a(t, x)
+    diffusion: B, // b(t, x)
+    diffusion_derivative: Option<D>, // b'(t, x) = db/dx. None: central difference quotient.
+
+    scheme: SdeScheme,
+}
+
+impl<A, B, D> Dynamics<A, B, D> {
+    // X(t + dt) given X(t) = x and the brownian increment dw over [t, t + dt].
+    fn increment<T: Float>(&self, t: T, x: T, dt: T, dw: T) -> T
+    where
+        A: Fn(T, T) -> T,
+        B: Fn(T, T) -> T,
+        D: Fn(T, T) -> T,
+    {
+        let a = (self.drift)(t, x);
+        let b = (self.diffusion)(t, x);
+        let half: T = scalar(0.5);
+
+        let euler = x + a * dt + b * dw;
+
+        match self.scheme {
+            SdeScheme::EulerMaruyama => euler,
+            SdeScheme::Milstein => euler + half * b * self.diffusion_slope(t, x) * (dw * dw - dt),
+            SdeScheme::RungeKutta => {
+                if dt <= T::zero() {
+                    return euler;
+                }
+                // supporting value replaces b * b' by a finite difference along the diffusion.
+                let support = x + a * dt + b * dt.sqrt();
+                let b_support = (self.diffusion)(t, support);
+
+                euler + (b_support - b) * (dw * dw - dt) / (scalar::<T, _>(2.0) * dt.sqrt())
+            }
+        }
+    }
+    // b'(t, x): supplied derivative, otherwise a central difference quotient.
+    fn diffusion_slope<T: Float>(&self, t: T, x: T) -> T
+    where
+        B: Fn(T, T) -> T,
+        D: Fn(T, T) -> T,
+    {
+        match &self.diffusion_derivative {
+            Some(derivative) => derivative(t, x),
+            None => {
+                let h = scalar::<T, _>(DERIVATIVE_STEP) * x.abs().max(T::one());
+                ( (self.diffusion)(t, x + h) - (self.diffusion)(t, x - h) ) / (h + h)
+            }
+        }
+    }
+    fn with_diffusion_derivative<E>(self, derivative: E) -> Dynamics<A, B, E> {
+        Dynamics {
+            drift: self.drift,
+            diffusion: self.diffusion,
+            diffusion_derivative: Some(derivative),
+            scheme: self.scheme,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Sde<A, B, T: Float = f64, R: Rng = DefaultRng, S: PathStorage<T> = Path<T>, D = fn(T, T) -> T>
+{
+    path: S, // generated points
+
+    initial: Point<T>, // starting point. initial.x: starting time, initial.y: X at that time.
+
+    dynamics: Dynamics<A, B, D>, // drift, diffusion and scheme
+
+    step: T, // distance per loop/step.
+    remaining: u32, // steps left to generate. counted, so float rounding cannot add a step.
+    loops: u32, // steps per generation. reset restores remaining to it.
+
+    rng: R, // source of randomness. seeded -> reproducible path.
+}
+
+impl<A, B, D, T, R, S> IntoIterator for Sde<A, B, T, R, S, D>
+where
+    T: Float,
+    R: Rng,
+    S: PathStorage<T>,
+{
+    type Item = Point<T>;
+    type IntoIter = ProcessIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.path.into_iter()
+    }
+}
+
+impl<A, B, D, T, R, S> Process for Sde<A, B, T, R, S, D>
+where
+    A: Fn(T, T) -> T,
+    B: Fn(T, T) -> T,
+    D: Fn(T, T) -> T,
+    T: Float,
+    R: Rng,
+    S: PathStorage<T>,
+{
+    type Scalar = T;
+    type Storage = S;
+
+    #[inline]
+    fn push_back(&mut self, point: Point<T>) -> usize {
+        self.path.push_back(point)
+    }
+    #[inline]
+    fn pop_back(&mut self) -> Option<Point<T>> {
+        self.path.pop_back()
+    }
+    fn path(&self) -> &S {
+        &self.path
+    }
+
+    // generate motion from given attributes
+    fn generate(&mut self) {
+        while self.remaining > 0 {
+            self.generate_single();
+        }
+    }
+    // generate single value
+    fn generate_single(&mut self) {
+        let cur = *self.path.tail().unwrap_or(&self.initial);
+
+        let z: T = standard_normal(&mut self.rng);
+        let dw = self.step.sqrt() * z;
+
+        let res = self.dynamics.increment(cur.x, cur.y, self.step, dw);
+
+        self.push_back( Point { x: cur.x + self.step, y: res } );
+
+        self.remaining = self.remaining.saturating_sub(1);
+    }
+    fn reset(&mut self) {
+        self.path.clear();
+
+        self.remaining = self.loops;
+    }
+}
+
+impl<A: Fn(T, T) -> T, B: Fn(T, T) -> T, T: Float> Sde<A, B, T> {
+    pub fn new(initial: Point<T>, drift: A, diffusion: B, step: T, loops: u32) -> Self {
+        Self::with_rng(initial, drift, diffusion, step, loops, default_rng())
+    }
+    // same seed and parameters -> identical path.
+    pub fn with_seed(initial: Point<T>, drift: A, diffusion: B, step: T, loops: u32, seed: u64) -> Self {
+        Self::with_rng(initial, drift, diffusion, step, loops, seeded_rng(seed))
+    }
+}
+
+impl<A: Fn(T, T) -> T, B: Fn(T, T) -> T, T: Float, R: Rng> Sde<A, B, T, R> {
+    // euler-maruyama. change with with_scheme.
+    pub fn with_rng(initial: Point<T>, drift: A, diffusion: B, step: T, loops: u32, rng: R) -> Self {
+        Self {
+            path: Path::with_capacity(loops as usize),
+
+            // model parameters
+            initial,
+            dynamics: Dynamics {
+                drift,
+                diffusion,
+                diffusion_derivative: None,
+                scheme: SdeScheme::EulerMaruyama,
+            },
+            step,
+            remaining: loops,
+            loops,
+
+            rng,
+        }
+    }
+}
+
+impl<A, B, D, T, R, S> Sde<A, B, T, R, S, D>
+where
+    A: Fn(T, T) -> T,
+    B: Fn(T, T) -> T,
+    D: Fn(T, T) -> T,
+    T: Float,
+    R: Rng,
+    S: PathStorage<T>,
+{
+    // swap the storage backend. points generated so far are dropped, generation starts over from the initial point.
+    pub fn with_storage<U: PathStorage<T>>(self, storage: U) -> Sde<A, B, T, R, U, D> {
+        Sde {
+            path: storage,
+
+            initial: self.initial,
+            dynamics: self.dynamics,
+            step: self.step,
+            remaining: self.loops,
+            loops: self.loops,
+            rng: self.rng,
+        }
+    }
+    // exact b'(t, x) = db/dx for the milstein scheme.
+    pub fn with_diffusion_derivative<E: Fn(T, T) -> T>(self, derivative: E) -> Sde<A, B, T, R, S, E> {
+        Sde {
+            path: self.path,
+
+            initial: self.initial,
+            dynamics: self.dynamics.with_diffusion_derivative(derivative),
+            step: self.step,
+            remaining: self.remaining,
+            loops: self.loops,
+            rng: self.rng,
+        }
+    }
+    pub fn with_scheme(mut self, scheme: SdeScheme) -> Self {
+        self.set_scheme(scheme);
+        self
+    }
+    pub fn set_scheme(&mut self, scheme: SdeScheme) {
+        self.dynamics.scheme = scheme;
+    }
+    pub fn set_step(&mut self, step: T) {
+        self.step = step;
+    }
+}
+
+// --- theory :
+// local error of a step: compare one step of length h with two steps of length h / 2 over the same brownian path.
+// too large -> halve the step, small -> grow it again. stiff or near-boundary regions get short steps.
+
+// --- here:
+// the brownian path is never redrawn. values of W already drawn at future times are kept on a stack,
+// a rejected step refines it with a brownian bridge midpoint (brownian tree), so the final path is
+// consistent with every value of W the integrator has looked at. points have non-uniform x spacing.
+#[derive(Debug, Clone)]
+pub struct AdaptiveSde<A, B, T: Float = f64, R: Rng = DefaultRng, S: PathStorage<T> = Path<T>, D = fn(T, T) -> T>
+{
+    path: S, // generated points
+
+    initial: Point<T>, // starting point. initial.x: starting time, initial.y: X at that time.
+
+    dynamics: Dynamics<A, B, D>, // drift, diffusion and scheme
+
+    tolerance: T, // accepted local error, relative to max(1, |X|).
+    step: T, // length of the next trial step.
+    max_step: T, // upper limit for the step, also its starting value.
+    min_step: T, // lower limit for the step. steps this short are accepted regardless of the error.
+
+    current_dist: T, // current time ( -> as total distance )
+    total_dist: T, // total time used for generation. ( -> as total distance.)
+
+    brownian: T, // W at the current time
+    future: Vec<(T, T)>, // (time, W) drawn ahead of the current time. nearest on top.
+    rejected: usize, // number of rejected trial steps
+
+    rng: R, // source of randomness. seeded -> reproducible path.
+}
+
+impl<A, B, D, T, R, S> IntoIterator for AdaptiveSde<A, B, T, R, S, D>
+where
+    T: Float,
+    R: Rng,
+    S: PathStorage<T>,
+{
+    type Item = Point<T>;
+    type IntoIter = ProcessIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.path.into_iter()
+    }
+}
+
+impl<A, B, D, T, R, S> Process for AdaptiveSde<A, B, T, R, S, D>
+where
+    A: Fn(T, T) -> T,
+    B: Fn(T, T) -> T,
+    D: Fn(T, T) -> T,
+    T: Float,
+    R: Rng,
+    S: PathStorage<T>,
+{
+    type Scalar = T;
+    type Storage = S;
+
+    #[inline]
+    fn push_back(&mut self, point: Point<T>) -> usize {
+        self.path.push_back(point)
+    }
+    #[inline]
+    fn pop_back(&mut self) -> Option<Point<T>> {
+        self.path.pop_back()
+    }
+    fn path(&self) -> &S {
+        &self.path
+    }
+    fn reset(&mut self) {
+        self.path.clear();
+
+        self.current_dist = T::zero();
+        self.step = self.max_step;
+        self.brownian = T::zero();
+        self.future.clear();
+        self.rejected = 0;
+    }
+    // generate steps until total distance is reached.
+    fn generate(&mut self) {
+        while self.current_dist < self.total_dist {
+            self.generate_single();
+        }
+    }
+    // generate single accepted step. rejected trial steps are retried with half the length.
+    fn generate_single(&mut self) {
+        if self.current_dist >= self.total_dist {
+            return;
+        }
+        let cur = *self.path.tail().unwrap_or(&self.initial);
+        let half: T = scalar(0.5);
+
+        loop {
+            // never step past the end or over a brownian value drawn earlier.
+            let mut end = (self.current_dist + self.step).min(self.total_dist);
+            if let Some(&(next, _)) = self.future.last() {
+                end = end.min(next);
+            }
+            let h = end - self.current_dist;
+            let mid = self.current_dist + h * half;
+
+            let w_end = self.brownian_at(end);
+            let w_mid = self.brownian_at(mid);
+
+            let t = self.initial.x + self.current_dist;
+            let coarse = self.dynamics.increment(t, cur.y, h, w_end - self.brownian);
+            let first = self.dynamics.increment(t, cur.y, h * half, w_mid - self.brownian);
+            let fine = self.dynamics.increment(t + h * half, first, h * half, w_end - w_mid);
+
+            let error = (coarse - fine).abs();
+            let allowed = self.tolerance * fine.abs().max(T::one());
+
+            if error <= allowed || h <= self.min_step {
+                // drop every stacked value at or behind the new time. mid and end may coincide at float
+                // resolution, so there need not be two of them.
+                while self.future.last().is_some_and(|&(t, _)| t <= end) {
+                    self.future.pop();
+                }
+
+                self.current_dist = end;
+                self.brownian = w_end;
+
+                // well within tolerance -> try a longer step next time.
+                if error <= allowed * half {
+                    self.step = (h + h).min(self.max_step);
+                }
+                self.push_back( Point { x: self.initial.x + end, y: fine } );
+                return;
+            }
+
+            // rejected: keep both brownian values on the stack, retry up to the midpoint.
+            self.rejected += 1;
+            self.step = (h * half).max(self.min_step);
+        }
+    }
+}
+
+impl<A: Fn(T, T) -> T, B: Fn(T, T) -> T, T: Float> AdaptiveSde<A, B, T> {
+    pub fn new(initial: Point<T>, drift: A, diffusion: B, max_step: T, tolerance: T, total_dist: T) -> Self {
+        Self::with_rng(initial, drift, diffusion, max_step, tolerance, total_dist, default_rng())
+    }
+    // same seed and parameters -> identical path.
+    pub fn with_seed(initial: Point<T>, drift: A, diffusion: B, max_step: T, tolerance: T, total_dist: T, seed: u64) -> Self {
+        Self::with_rng(initial, drift, diffusion, max_step, tolerance, total_dist, seeded_rng(seed))
+    }
+}
+
+impl<A: Fn(T, T) -> T, B: Fn(T, T) -> T, T: Float, R: Rng> AdaptiveSde<A, B, T, R> {
+    // euler-maruyama, min_step = max_step / 2^20. change with with_scheme and with_min_step.
+    pub fn with_rng(initial: Point<T>, drift: A, diffusion: B, max_step: T, tolerance: T, total_dist: T, rng: R) -> Self {
+        assert!(max_step > T::zero(), "step must be positive");
+        assert!(tolerance > T::zero(), "tolerance must be positive");
+
+        Self {
+            path: Path::new(),
+
+            // model parameters
+            initial,
+            dynamics: Dynamics {
+                drift,
+                diffusion,
+                diffusion_derivative: None,
+                scheme: SdeScheme::EulerMaruyama,
+            },
+            tolerance,
+            step: max_step,
+            max_step,
+            min_step: max_step / scalar(1u32 << 20),
+            current_dist: T::zero(),
+            total_dist,
+
+            brownian: T::zero(),
+            future: Vec::new(),
+            rejected: 0,
+
+            rng,
+        }
+    }
+}
+
+impl<A, B, D, T, R, S> AdaptiveSde<A, B, T, R, S, D>
+where
+    A: Fn(T, T) -> T,
+    B: Fn(T, T) -> T,
+    D: Fn(T, T) -> T,
+    T: Float,
+    R: Rng,
+    S: PathStorage<T>,
+{
+    // swap the storage backend. points generated so far are dropped, generation starts over from the initial point.
+    pub fn with_storage<U: PathStorage<T>>(self, storage: U) -> AdaptiveSde<A, B, T, R, U, D> {
+        AdaptiveSde {
+            path: storage,
+
+            initial: self.initial,
+            dynamics: self.dynamics,
+            tolerance: self.tolerance,
+            step: self.max_step,
+            max_step: self.max_step,
+            min_step: self.min_step,
+            current_dist: T::zero(),
+            total_dist: self.total_dist,
+            brownian: T::zero(),
+            future: Vec::new(),
+            rejected: 0,
+            rng: self.rng,
+        }
+    }
+    // exact b'(t, x) = db/dx for the milstein scheme.
+    pub fn with_diffusion_derivative<E: Fn(T, T) -> T>(self, derivative: E) -> AdaptiveSde<A, B, T, R, S, E> {
+        AdaptiveSde {
+            path: self.path,
+
+            initial: self.initial,
+            dynamics: self.dynamics.with_diffusion_derivative(derivative),
+            tolerance: self.tolerance,
+            step: self.step,
+            max_step: self.max_step,
+            min_step: self.min_step,
+            current_dist: self.current_dist,
+            total_dist: self.total_dist,
+            brownian: self.brownian,
+            future: self.future,
+            rejected: self.rejected,
+            rng: self.rng,
+        }
+    }
+    pub fn with_scheme(mut self, scheme: SdeScheme) -> Self {
+        self.set_scheme(scheme);
+        self
+    }
+    pub fn with_min_step(mut self, min_step: T) -> Self {
+        self.set_min_step(min_step);
+        self
+    }
+    pub fn set_scheme(&mut self, scheme: SdeScheme) {
+        self.dynamics.scheme = scheme;
+    }
+    pub fn set_min_step(&mut self, min_step: T) {
+        assert!(min_step > T::zero() && min_step <= self.max_step, "min step must lie in (0, max step]");
+
+        self.min_step = min_step;
+    }
+    pub fn set_tolerance(&mut self, tolerance: T) {
+        self.tolerance = tolerance;
+    }
+    pub fn set_total_dist(&mut self, total_dist: T) {
+        self.total_dist = total_dist;
+    }
+    // number of trial steps rejected so far
+    pub fn rejected(&self) -> usize {
+        self.rejected
+    }
+    // W at time t (relative to the start), current_dist < t <= next stacked time.
+    // drawn from the bridge to the next stacked value, or freely beyond the stack. pushed onto the stack.
+    fn brownian_at(&mut self, t: T) -> T {
+        let current = Point { x: self.current_dist, y: self.brownian };
+
+        let w = match self.future.last() {
+            Some(&(next, w)) if next == t => return w,
+            Some(&(next, w)) => crate::brownian::bridge_sample(&mut self.rng, current, Point { x: next, y: w }, t, T::one()),
+            None => {
+                let z: T = standard_normal(&mut self.rng);
+                self.brownian + (t - self.current_dist).sqrt() * z
+            }
+        };
+        self.future.push( (t, w) );
+        w
+    }
+}
@@ -0,0 +1,250 @@
+// storage backends for generated points.
+// every process is generic over its storage, so the same model can keep its full path,
+// only the most recent points, or nothing but the point needed for the next step.
+
+use std::collections::VecDeque;
+
+use num_traits::Float;
+
+use crate::base::{Point, ProcessIter};
+
+// iterator over stored points, front to back.
+pub type StorageIter<'a, T> = std::iter::Chain<std::slice::Iter<'a, Point<T>>, std::slice::Iter<'a, Point<T>>>;
+
+pub trait PathStorage<T: Float>: IntoIterator<Item = Point<T>, IntoIter = ProcessIter<T>> {
+    fn push_back(&mut self, point: Point<T>) -> usize; // add an element to back. returns number of stored elements.
+
+    fn pop_back(&mut self) -> Option<Point<T>>; // remove an element from back
+
+    fn tail(&self) -> Option<&Point<T>>; // last element. processes continue from here.
+
+    fn len(&self) -> usize; // number of stored elements
+
+    fn clear(&mut self); // remove all elements
+
+    fn as_slices(&self) -> (&[Point<T>], &[Point<T>]); // stored elements in order, split in at most two parts.
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn iter(&self) -> StorageIter<'_, T> {
+        let (front, back) = self.as_slices();
+        front.iter().chain(back.iter())
+    }
+}
+
+// generated points of a process, stored contiguously in order of generation.
+// owns its points: dropping or cloning a path drops or clones all of them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Path<T: Float = f64> {
+    points: Vec<Point<T>>,
+}
+
+impl<T: Float> Path<T> {
+    pub fn new() -> Self {
+        Self {
+            points: Vec::new(),
+        }
+    }
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            points: Vec::with_capacity(capacity),
+        }
+    }
+    // add a point to back. returns the new length.
+    #[inline]
+    pub fn push_back(&mut self, point: Point<T>) -> usize {
+        self.points.push(point);
+        self.points.len()
+    }
+    // remove the point at back. head and tail are both gone once the last point is removed.
+    #[inline]
+    pub fn pop_back(&mut self) -> Option<Point<T>> {
+        self.points.pop()
+    }
+    // first point
+    pub fn head(&self) -> Option<&Point<T>> {
+        self.points.first()
+    }
+    // last point
+    pub fn tail(&self) -> Option<&Point<T>> {
+        self.points.last()
+    }
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+    pub fn clear(&mut self) {
+        self.points.clear();
+    }
+    pub fn iter(&self) -> std::slice::Iter<'_, Point<T>> {
+        self.points.iter()
+    }
+    pub fn as_slice(&self) -> &[Point<T>] {
+        &self.points
+    }
+}
+
+impl<T: Float> Default for Path<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> PathStorage<T> for Path<T> {
+    #[inline]
+    fn push_back(&mut self, point: Point<T>) -> usize {
+        Path::push_back(self, point)
+    }
+    #[inline]
+    fn pop_back(&mut self) -> Option<Point<T>> {
+        Path::pop_back(self)
+    }
+    fn tail(&self) -> Option<&Point<T>> {
+        Path::tail(self)
+    }
+    fn len(&self) -> usize {
+        Path::len(self)
+    }
+    fn clear(&mut self) {
+        Path::clear(self)
+    }
+    fn as_slices(&self) -> (&[Point<T>], &[Point<T>]) {
+        (&self.points, &[])
+    }
+}
+
+impl<'a, T: Float> IntoIterator for &'a Path<T> {
+    type Item = &'a Point<T>;
+    type IntoIter = std::slice::Iter<'a, Point<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.points.iter()
+    }
+}
+
+impl<T: Float> IntoIterator for Path<T> {
+    type Item = Point<T>;
+    type IntoIter = ProcessIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ProcessIter::from(self.points)
+    }
+}
+
+// keeps only the most recent `capacity` points. older points are dropped as new ones arrive.
+// -> bounded memory for long running generation, e.g. a scrolling plot.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RingBuffer<T: Float = f64> {
+    points: VecDeque<Point<T>>,
+    capacity: usize,
+}
+
+impl<T: Float> RingBuffer<T> {
+    pub fn new(capacity: usize) -> Self {
+        // at least the last point is needed to continue the process.
+        assert!(capacity > 0, "ring buffer capacity must be at least 1");
+
+        Self {
+            points: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl<T: Float> PathStorage<T> for RingBuffer<T> {
+    #[inline]
+    fn push_back(&mut self, point: Point<T>) -> usize {
+        if self.points.len() == self.capacity {
+            self.points.pop_front();
+        }
+        self.points.push_back(point);
+        self.points.len()
+    }
+    #[inline]
+    fn pop_back(&mut self) -> Option<Point<T>> {
+        self.points.pop_back()
+    }
+    fn tail(&self) -> Option<&Point<T>> {
+        self.points.back()
+    }
+    fn len(&self) -> usize {
+        self.points.len()
+    }
+    fn clear(&mut self) {
+        self.points.clear();
+    }
+    fn as_slices(&self) -> (&[Point<T>], &[Point<T>]) {
+        self.points.as_slices()
+    }
+}
+
+impl<T: Float> IntoIterator for RingBuffer<T> {
+    type Item = Point<T>;
+    type IntoIter = ProcessIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ProcessIter::from(Vec::from(self.points))
+    }
+}
+
+// keeps nothing but the latest point, which the process needs for its next step.
+// -> for consumers that read each point via tail() right after generate_single().
+#[derive(Debug, Clone, PartialEq)]
+pub struct Streaming<T: Float = f64> {
+    last: Option<Point<T>>,
+}
+
+impl<T: Float> Streaming<T> {
+    pub fn new() -> Self {
+        Self {
+            last: None,
+        }
+    }
+}
+
+impl<T: Float> Default for Streaming<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> PathStorage<T> for Streaming<T> {
+    #[inline]
+    fn push_back(&mut self, point: Point<T>) -> usize {
+        self.last = Some(point);
+        1
+    }
+    // the point before the latest one is not kept. after popping, the process restarts from its initial point.
+    #[inline]
+    fn pop_back(&mut self) -> Option<Point<T>> {
+        self.last.take()
+    }
+    fn tail(&self) -> Option<&Point<T>> {
+        self.last.as_ref()
+    }
+    fn len(&self) -> usize {
+        self.last.is_some() as usize
+    }
+    fn clear(&mut self) {
+        self.last = None;
+    }
+    fn as_slices(&self) -> (&[Point<T>], &[Point<T>]) {
+        (self.last.as_slice(), &[])
+    }
+}
+
+impl<T: Float> IntoIterator for Streaming<T> {
+    type Item = Point<T>;
+    type IntoIter = ProcessIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ProcessIter::from(self.last.into_iter().collect::<Vec<_>>())
+    }
+}
@@ -0,0 +1,84 @@
+use process_lib::base::*;
+use process_lib::bernoulli::*;
+
+const TRIALS: u32 = 2000;
+
+fn seeded(probability: f64, seed: u64) -> Bernoulli {
+    let mut trials = Bernoulli::with_seed(Point { x: 0.0, y: 0.2 }, Point { x: 1.0, y: 0.1 }, TRIALS, seed)
+        .with_probability(probability);
+    trials.generate();
+    trials
+}
+
+// the share of successes estimates p.
+#[test]
+fn bernoulli_success_rate_matches_probability() {
+    for probability in [0.1, 0.5, 0.8] {
+        let trials = seeded(probability, 3);
+        let rate = trials.successes() as f64 / TRIALS as f64;
+
+        assert!((rate - probability).abs() < 0.03, "rate {} != {}", rate, probability);
+    }
+}
+
+// the counting process climbs by step.y at every success and stays otherwise.
+#[test]
+fn bernoulli_counting_process_increments() {
+    let trials = seeded(0.3, 5);
+    let counting = trials.counting_process();
+
+    let mut previous = 0.2;
+    for (point, success) in counting.iter().zip(trials.trials()) {
+        let expected = if success { previous + 0.1 } else { previous };
+        assert_eq!(point.y, expected);
+        previous = point.y;
+    }
+    assert!(counting.iter().zip(trials.path().iter()).all(|(count, trial)| count.x == trial.x));
+}
+
+// k-th success and run lengths agree with the recorded outcomes.
+#[test]
+fn bernoulli_successes_and_runs_follow_outcomes() {
+    let trials = seeded(0.4, 7);
+    let outcomes: Vec<bool> = trials.trials().collect();
+    let xs: Vec<f64> = trials.path().iter().map(|point| point.x).collect();
+
+    let success_xs: Vec<f64> = xs.iter().zip(&outcomes).filter(|(_, &success)| success).map(|(&x, _)| x).collect();
+    assert_eq!(trials.time_of_success(0), None);
+    assert_eq!(trials.time_of_success(1), success_xs.first().copied());
+    assert_eq!(trials.time_of_success(success_xs.len()), success_xs.last().copied());
+    assert_eq!(trials.time_of_success(success_xs.len() + 1), None);
+
+    // runs: maximal chunks of equal outcomes.
+    let runs: Vec<&[bool]> = outcomes.chunk_by(|a, b| a == b).collect();
+    let longest = |outcome: bool| runs.iter().filter(|run| run[0] == outcome).map(|run| run.len()).max().unwrap_or(0);
+    let stats = trials.run_stats();
+
+    assert_eq!(stats.runs, runs.len());
+    assert_eq!(stats.success_runs, runs.iter().filter(|run| run[0]).count());
+    assert_eq!(stats.longest_success, longest(true));
+    assert_eq!(stats.longest_failure, longest(false));
+}
+
+// y values that miss the outcomes by rounding are classified by the nearer one.
+#[test]
+fn bernoulli_push_back_takes_nearest_outcome() {
+    let mut trials = Bernoulli::new(Point { x: 0.0, y: 0.2 }, Point { x: 1.0, y: 0.1 }, 0);
+
+    trials.push_back(Point { x: 0.0, y: 0.3 });
+    trials.push_back(Point { x: 1.0, y: 0.2 });
+    trials.push_back(Point { x: 2.0, y: 0.29 });
+
+    assert_eq!(trials.trials().collect::<Vec<_>>(), vec![true, false, true]);
+}
+
+// the outcome history is bounded by the storage.
+#[test]
+fn bernoulli_outcomes_follow_storage() {
+    let mut trials = Bernoulli::with_seed(Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 1.0 }, 100, 1)
+        .with_storage(RingBuffer::new(8));
+    trials.generate();
+
+    assert_eq!(trials.trials().count(), 8);
+    assert!(trials.trials().zip(trials.path().iter()).all(|(success, point)| success == (point.y == 1.0)));
+}
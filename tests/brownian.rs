@@ -0,0 +1,218 @@
+mod common;
+
+use common::*;
+use process_lib::base::*;
+use process_lib::brownian::*;
+use process_lib::ensemble::simulate_paths;
+
+const DRIFT: f64 = 0.1;
+const VOLATILITY: f64 = 0.3;
+const STEP: f64 = 1.0;
+const STEPS: u32 = 500;
+const PATHS: u64 = 200;
+
+// log-returns ~ N( (mu - sigma^2 / 2) * dt, sigma^2 * dt )
+fn assert_log_normal(samples: &[f64]) {
+    let expected_mean = (DRIFT - 0.5 * VOLATILITY * VOLATILITY) * STEP;
+    let expected_variance = VOLATILITY * VOLATILITY * STEP;
+
+    let (mean, variance) = mean_variance(samples);
+
+    assert_eq!(samples.len(), (PATHS * STEPS as u64) as usize);
+    assert!((mean - expected_mean).abs() < 0.005, "mean {} != {}", mean, expected_mean);
+    assert!((variance - expected_variance).abs() < 0.003, "variance {} != {}", variance, expected_variance);
+}
+
+#[test]
+fn gbm_log_returns_match_theory() {
+    let initial = Point { x: 0.0, y: 1.0 };
+    let mut samples = Vec::new();
+
+    for seed in 0..PATHS {
+        let mut gbm = GeometricBrownianMotion::with_seed(initial, DRIFT, VOLATILITY, STEP, STEPS, seed);
+        gbm.generate();
+
+        samples.extend(log_returns(initial.y, gbm.into_iter()));
+    }
+
+    assert_log_normal(&samples);
+}
+
+#[test]
+fn gbm_storage_backends_agree() {
+    let initial = Point { x: 0.0, y: 1.0 };
+
+    let mut full = GeometricBrownianMotion::with_seed(initial, DRIFT, VOLATILITY, STEP, STEPS, 7);
+    let mut ring = GeometricBrownianMotion::with_seed(initial, DRIFT, VOLATILITY, STEP, STEPS, 7)
+        .with_storage(RingBuffer::new(10));
+    let mut streaming = GeometricBrownianMotion::with_seed(initial, DRIFT, VOLATILITY, STEP, STEPS, 7)
+        .with_storage(Streaming::new());
+
+    full.generate();
+    ring.generate();
+    streaming.generate();
+
+    let points = full.path().as_slice();
+
+    assert_eq!(points.len(), STEPS as usize);
+    assert_eq!(ring.path().len(), 10);
+    assert!(ring.path().iter().eq(points[points.len() - 10..].iter()));
+    assert_eq!(streaming.path().len(), 1);
+    assert_eq!(streaming.path().tail(), points.last());
+}
+
+#[test]
+fn gbm_reset_rewinds_the_step_count() {
+    let mut gbm = GeometricBrownianMotion::with_seed(Point { x: 0.0, y: 1.0 }, DRIFT, VOLATILITY, STEP, STEPS, 7);
+
+    gbm.generate();
+    gbm.reset();
+    assert!(gbm.path().is_empty());
+
+    gbm.generate();
+    assert_eq!(gbm.path().len(), STEPS as usize);
+}
+
+// singular but positive semi-definite: the third variable is 0.6 * first + 0.8 * second.
+fn dependent_correlation<T: num_traits::Float>() -> Vec<Vec<T>> {
+    let v = |x: f64| T::from(x).unwrap();
+    vec![vec![v(1.0), v(0.0), v(0.6)], vec![v(0.0), v(1.0), v(0.8)], vec![v(0.6), v(0.8), v(1.0)]]
+}
+
+// L * L^T reproduces the matrix.
+fn assert_factorises<T: num_traits::Float + std::fmt::Debug>(matrix: &[Vec<T>], tolerance: T) {
+    let lower = cholesky(matrix);
+    let n = matrix.len();
+
+    for i in 0..n {
+        for j in 0..n {
+            let product = (0..n).fold(T::zero(), |sum, k| sum + lower[i][k] * lower[j][k]);
+            assert!((product - matrix[i][j]).abs() <= tolerance, "({}, {}): {:?} != {:?}", i, j, product, matrix[i][j]);
+        }
+        assert!((i + 1..n).all(|j| lower[i][j] == T::zero()), "factor is lower triangular");
+    }
+}
+
+#[test]
+fn cholesky_factorises_semi_definite_matrix() {
+    assert_factorises(&dependent_correlation::<f64>(), 1e-12);
+    assert_factorises(&dependent_correlation::<f32>(), 1e-6);
+}
+
+#[test]
+#[should_panic(expected = "matrix is not positive semi-definite")]
+fn cholesky_rejects_indefinite_matrix() {
+    cholesky(&[vec![1.0, 0.9, 0.9], vec![0.9, 1.0, -0.9], vec![0.9, -0.9, 1.0]]);
+}
+
+// log-returns of the assets are correlated like their drivers.
+#[test]
+fn multi_gbm_log_returns_follow_correlation() {
+    let correlation_matrix = dependent_correlation::<f64>();
+    let initial = MultiPoint { x: 0.0, y: vec![1.0; 3] };
+    let params = MultiGbmParams {
+        drift: vec![DRIFT; 3],
+        volatility: vec![0.1, 0.2, 0.3],
+        correlation: correlation_matrix.clone(),
+    };
+
+    let mut basket = MultiGeometricBrownianMotion::with_seed(initial, params, 0.01, 20000, 3);
+    basket.generate();
+
+    let returns: Vec<Vec<f64>> = (0..3).map(|asset| log_returns(1.0, basket.asset_path(asset).into_iter())).collect();
+
+    for i in 0..3 {
+        for j in i + 1..3 {
+            let sample = correlation(&returns[i], &returns[j]);
+            assert!((sample - correlation_matrix[i][j]).abs() < 0.03, "corr({}, {}) {} != {}", i, j, sample, correlation_matrix[i][j]);
+        }
+    }
+}
+
+// the f32 basket accepts the same singular matrix.
+#[test]
+fn multi_gbm_accepts_f32_semi_definite_correlation() {
+    let initial = MultiPoint { x: 0.0f32, y: vec![1.0; 3] };
+    let params = MultiGbmParams { drift: vec![0.1; 3], volatility: vec![0.2; 3], correlation: dependent_correlation() };
+
+    let mut basket = MultiGeometricBrownianMotion::with_seed(initial, params, 0.01, 10, 1);
+    basket.generate();
+
+    assert!(basket.points().iter().flat_map(|point| &point.y).all(|y| y.is_finite()));
+}
+
+// the last step lands exactly on the end point, whatever rounding the steps accumulate.
+#[test]
+fn bridge_hits_end_point_exactly() {
+    let initial = Point { x: 0.1, y: 1.0 };
+    let end = Point { x: 0.7, y: -2.3 };
+
+    for seed in 0..20 {
+        let mut bridge = BrownianBridge::with_seed(initial, end, 0.5, 7, seed);
+        bridge.generate();
+
+        assert_eq!(bridge.path().len(), 7);
+        assert_eq!(bridge.path().tail(), Some(&end));
+    }
+
+    let end32 = Point { x: 0.7f32, y: -2.3 };
+    let mut bridge = BrownianBridge::with_seed(Point { x: 0.1f32, y: 1.0 }, end32, 0.5, 3, 1);
+    bridge.generate();
+    assert_eq!(bridge.path().tail(), Some(&end32));
+}
+
+// every level doubles the intervals and keeps the points it refines.
+#[test]
+fn bridge_refine_keeps_original_points() {
+    let mut gbm = GeometricBrownianMotion::with_seed(Point { x: 0.0, y: 1.0 }, DRIFT, VOLATILITY, STEP, 10, 7);
+    gbm.generate();
+
+    let coarse: Vec<Point> = gbm.path().iter().copied().collect();
+    let levels = 3;
+    let fine = bridge_refine(coarse.iter().copied(), VOLATILITY, levels, &mut seeded_rng(1));
+
+    assert_eq!(fine.len(), (coarse.len() - 1) * 2usize.pow(levels) + 1);
+    assert!(fine.iter().step_by(2usize.pow(levels)).eq(coarse.iter()));
+    assert!(fine.windows(2).all(|pair| pair[0].x < pair[1].x));
+}
+
+// swapping the storage mid-way starts a full generation from the initial point.
+#[test]
+fn gbm_with_storage_starts_over() {
+    let initial = Point { x: 0.0, y: 1.0 };
+    let mut gbm = GeometricBrownianMotion::with_seed(initial, DRIFT, VOLATILITY, STEP, 10, 7);
+    for _ in 0..4 {
+        gbm.generate_single();
+    }
+
+    let mut ring = gbm.with_storage(RingBuffer::new(20));
+    ring.generate();
+
+    assert_eq!(ring.path().len(), 10);
+    assert_eq!(ring.path().tail().map(|tail| tail.x), Some(10.0));
+}
+
+// W(T) - W(0) ~ N(mu * T, sigma^2 * T). no drift: mu = 0.
+fn assert_sbm_terminal(drift: Option<f64>) {
+    let (scale, step, loops) = (0.7, 0.05, 40);
+    let t = step * loops as f64;
+
+    let ensemble = simulate_paths(20000, 11, |rng| StandardBrownianMotion::with_rng(Point { x: 0.0, y: 2.0 }, drift, scale, step, loops, rng));
+    let (mean, variance) = mean_variance(&ensemble.terminal());
+
+    let expected_mean = 2.0 + drift.unwrap_or(0.0) * t;
+    let expected_variance = scale * scale * t;
+
+    assert!((mean - expected_mean).abs() < 0.03, "mean {} != {}", mean, expected_mean);
+    assert!((variance / expected_variance - 1.0).abs() < 0.05, "variance {} != {}", variance, expected_variance);
+}
+
+#[test]
+fn sbm_terminal_moments_without_drift() {
+    assert_sbm_terminal(None);
+}
+
+#[test]
+fn sbm_terminal_moments_with_drift() {
+    assert_sbm_terminal(Some(0.8));
+}
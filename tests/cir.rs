@@ -0,0 +1,51 @@
+mod common;
+
+use common::mean_variance;
+use process_lib::base::*;
+use process_lib::cir::CoxIngersollRoss;
+use process_lib::ensemble::simulate_paths;
+
+const KAPPA: f64 = 1.5;
+const THETA: f64 = 0.04;
+const SIGMA: f64 = 0.3;
+const INITIAL: f64 = 0.1;
+const PATHS: usize = 20000;
+
+// exact scheme: X(t) given X(0) = x0 has
+// mean x0 * e^(-kappa t) + theta * (1 - e^(-kappa t)),
+// variance x0 * sigma^2 / kappa * (e^(-kappa t) - e^(-2 kappa t)) + theta * sigma^2 / (2 kappa) * (1 - e^(-kappa t))^2.
+fn assert_moments(step: f64, loops: u32) {
+    let t = step * loops as f64;
+    let decay = (-KAPPA * t).exp();
+
+    let ensemble = simulate_paths(PATHS, 5, |rng| {
+        CoxIngersollRoss::with_rng(Point { x: 0.0, y: INITIAL }, KAPPA, THETA, SIGMA, step, loops, rng)
+    });
+    let terminal = ensemble.terminal();
+    let (mean, variance) = mean_variance(&terminal);
+
+    let expected_mean = INITIAL * decay + THETA * (1.0 - decay);
+    let expected_variance = INITIAL * SIGMA * SIGMA / KAPPA * (decay - decay * decay)
+        + THETA * SIGMA * SIGMA / (2.0 * KAPPA) * (1.0 - decay).powi(2);
+
+    assert!(terminal.iter().all(|&x| x >= 0.0));
+    assert!((mean - expected_mean).abs() < 0.002, "mean {} != {}", mean, expected_mean);
+    assert!((variance / expected_variance - 1.0).abs() < 0.05, "variance {} != {}", variance, expected_variance);
+}
+
+#[test]
+fn cir_exact_single_step_moments() {
+    assert_moments(1.0, 1);
+}
+
+// many steps chain exact transitions: still exact.
+#[test]
+fn cir_exact_many_steps_moments() {
+    assert_moments(0.05, 20);
+}
+
+#[test]
+#[should_panic(expected = "kappa, theta and sigma must be positive")]
+fn cir_rejects_zero_kappa() {
+    CoxIngersollRoss::new(Point { x: 0.0, y: INITIAL }, 0.0, THETA, SIGMA, 0.1, 10);
+}
@@ -0,0 +1,36 @@
+// statistics shared by the test suites.
+// every suite compiles this module on its own and uses only part of it.
+#![allow(dead_code)]
+
+use process_lib::base::Point;
+
+// log-returns of a path, starting from the initial value.
+pub fn log_returns(initial: f64, values: impl Iterator<Item = Point>) -> Vec<f64> {
+    let mut prev = initial;
+
+    values
+        .map(|point| {
+            let ret = (point.y / prev).ln();
+            prev = point.y;
+            ret
+        })
+        .collect()
+}
+
+// sample mean and unbiased sample variance.
+pub fn mean_variance(samples: &[f64]) -> (f64, f64) {
+    let n = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / n;
+    let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+
+    (mean, variance)
+}
+
+// sample correlation of two equally long series.
+pub fn correlation(a: &[f64], b: &[f64]) -> f64 {
+    let (mean_a, var_a) = mean_variance(a);
+    let (mean_b, var_b) = mean_variance(b);
+    let covariance = a.iter().zip(b).map(|(x, y)| (x - mean_a) * (y - mean_b)).sum::<f64>() / (a.len() as f64 - 1.0);
+
+    covariance / (var_a * var_b).sqrt()
+}
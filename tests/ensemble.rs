@@ -0,0 +1,27 @@
+#![cfg(feature = "parallel")]
+
+use process_lib::base::*;
+use process_lib::brownian::GeometricBrownianMotion;
+use process_lib::ensemble::*;
+
+fn build_gbm(rng: DefaultRng) -> GeometricBrownianMotion {
+    GeometricBrownianMotion::with_rng(Point { x: 0.0, y: 1.0 }, 0.05, 0.2, 0.01, 100, rng)
+}
+
+fn parallel_with_threads(threads: usize) -> Ensemble {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .unwrap()
+        .install(|| simulate_paths_parallel(64, 11, build_gbm))
+}
+
+#[test]
+fn parallel_matches_serial_for_any_thread_count() {
+    let serial = simulate_paths(64, 11, build_gbm);
+
+    assert_eq!(serial.len(), 64);
+    assert_eq!(serial.steps(), 100);
+    assert_eq!(parallel_with_threads(1), serial);
+    assert_eq!(parallel_with_threads(4), serial);
+}
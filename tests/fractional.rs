@@ -0,0 +1,72 @@
+mod common;
+
+use common::mean_variance;
+use process_lib::base::*;
+use process_lib::ensemble::simulate_paths;
+use process_lib::fractional::*;
+
+const STEPS: u32 = 16;
+const PATHS: usize = 20000;
+
+fn ensemble(hurst: f64, method: FbmMethod, seed: u64) -> process_lib::ensemble::Ensemble {
+    simulate_paths(PATHS, seed, |rng| {
+        FractionalBrownianMotion::with_rng(Point { x: 0.0, y: 0.0 }, hurst, 1.0, 1.0, STEPS, rng).with_method(method)
+    })
+}
+
+// self-similarity: Var B_H(n) = n^2H for unit steps and scale.
+fn assert_terminal_variance(hurst: f64, method: FbmMethod) {
+    let (mean, variance) = mean_variance(&ensemble(hurst, method, 3).terminal());
+    let expected = (STEPS as f64).powf(2.0 * hurst);
+
+    assert!(mean.abs() < 0.05 * expected.sqrt(), "mean {} != 0", mean);
+    assert!((variance / expected - 1.0).abs() < 0.05, "variance {} != {}", variance, expected);
+}
+
+// neighbouring increments: gamma(1) = ( 2^2H - 2 ) / 2.
+fn assert_lag_one_covariance(hurst: f64, method: FbmMethod) {
+    let ensemble = ensemble(hurst, method, 5);
+    let (mut sum, mut count) = (0.0, 0.0);
+
+    for path in ensemble.paths() {
+        let mut previous = 0.0;
+        let increments: Vec<f64> = path
+            .iter()
+            .map(|point| {
+                let increment = point.y - previous;
+                previous = point.y;
+                increment
+            })
+            .collect();
+
+        for pair in increments.windows(2) {
+            sum += pair[0] * pair[1];
+            count += 1.0;
+        }
+    }
+    let covariance = sum / count;
+    let expected = 0.5 * (2f64.powf(2.0 * hurst) - 2.0);
+
+    assert!((covariance - expected).abs() < 0.02, "lag-1 covariance {} != {}", covariance, expected);
+}
+
+#[test]
+fn fbm_exact_terminal_variance() {
+    assert_terminal_variance(0.75, FbmMethod::Exact);
+    assert_terminal_variance(0.3, FbmMethod::Exact);
+}
+
+#[test]
+fn fbm_exact_lag_one_covariance() {
+    assert_lag_one_covariance(0.75, FbmMethod::Exact);
+    assert_lag_one_covariance(0.3, FbmMethod::Exact);
+}
+
+// memory covers the whole path: hosking prediction is exact too.
+#[test]
+fn fbm_approximate_matches_with_full_memory() {
+    let method = FbmMethod::Approximate { memory: STEPS as usize };
+
+    assert_terminal_variance(0.75, method);
+    assert_lag_one_covariance(0.75, method);
+}
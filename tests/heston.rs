@@ -0,0 +1,79 @@
+use process_lib::base::*;
+use process_lib::heston::*;
+
+const PARAMS: HestonParams = HestonParams {
+    drift: 0.05,
+    kappa: 1.0,
+    theta: 0.04,
+    xi: 0.6, // 2 * kappa * theta < xi^2: the variance hits zero
+    rho: -0.7,
+};
+const STEP: f64 = 0.01;
+const LOOPS: u32 = 1000;
+
+// the variance is truncated at zero and generated in pairs with the price.
+#[test]
+fn heston_variance_path_is_non_negative_and_aligned() {
+    let mut heston = Heston::with_seed(Point { x: 0.0, y: 100.0 }, 0.04, PARAMS, STEP, LOOPS, 3);
+    heston.generate();
+
+    let prices = heston.path();
+    let variances = heston.variance_path();
+
+    assert!(!heston.feller_condition());
+    assert_eq!(prices.len(), LOOPS as usize);
+    assert_eq!(variances.len(), prices.len());
+    assert!(prices.iter().zip(variances.iter()).all(|(price, variance)| price.x == variance.x));
+    assert!(variances.iter().all(|variance| variance.y >= 0.0));
+    assert!(variances.iter().any(|variance| variance.y == 0.0));
+}
+
+// xi = 0 and v0 = theta: constant variance, the price is a gbm with sigma = sqrt(theta).
+// the price uses the first of the two normals drawn per step.
+#[test]
+fn heston_without_vol_of_vol_is_gbm() {
+    let params = HestonParams { xi: 0.0, ..PARAMS };
+    let mut heston = Heston::with_seed(Point { x: 0.0, y: 100.0 }, params.theta, params, STEP, LOOPS, 5);
+    heston.generate();
+
+    let sigma = params.theta.sqrt();
+    let mut rng = seeded_rng(5);
+    let mut log_price = 100f64.ln();
+
+    for (price, variance) in heston.path().iter().zip(heston.variance_path().iter()) {
+        let z1: f64 = standard_normal(&mut rng);
+        let _z3: f64 = standard_normal(&mut rng);
+        log_price += (params.drift - 0.5 * sigma * sigma) * STEP + sigma * STEP.sqrt() * z1;
+
+        assert_eq!(variance.y, params.theta);
+        assert!((price.y.ln() - log_price).abs() < 1e-9, "{} != {}", price.y.ln(), log_price);
+    }
+}
+
+// a pushed price repeats the last variance, popping removes both.
+#[test]
+fn heston_push_back_repeats_variance() {
+    let mut heston = Heston::with_seed(Point { x: 0.0, y: 100.0 }, 0.04, PARAMS, STEP, 10, 1);
+    heston.generate();
+
+    let last = *heston.variance_path().tail().unwrap();
+    heston.push_back(Point { x: 0.5, y: 120.0 });
+
+    assert_eq!(heston.variance_path().tail(), Some(&Point { x: 0.5, y: last.y }));
+
+    heston.pop_back();
+    assert_eq!(heston.variance_path().tail(), Some(&last));
+    assert_eq!(heston.variance_path().len(), heston.path().len());
+}
+
+#[test]
+#[should_panic(expected = "kappa, theta and xi must not be negative")]
+fn heston_rejects_negative_theta() {
+    Heston::new(Point { x: 0.0, y: 100.0 }, 0.04, HestonParams { theta: -0.04, ..PARAMS }, STEP, LOOPS);
+}
+
+#[test]
+#[should_panic(expected = "starting variance must not be negative")]
+fn heston_rejects_negative_initial_variance() {
+    Heston::new(Point { x: 0.0, y: 100.0 }, -0.01, PARAMS, STEP, LOOPS);
+}
@@ -0,0 +1,145 @@
+use process_lib::base::*;
+use process_lib::markov::*;
+
+const A: f64 = 0.3; // p_01
+const B: f64 = 0.1; // p_10
+
+fn two_state(steps: u32, seed: u64) -> MarkovChain {
+    MarkovChain::with_seed(vec![vec![1.0 - A, A], vec![B, 1.0 - B]], vec![0.0, 1.0], 0, 0.0, 1.0, steps, seed)
+}
+
+// pi = (b, a) / (a + b)
+#[test]
+fn markov_two_state_stationary_distribution() {
+    let pi = two_state(0, 1).stationary_distribution().unwrap();
+
+    assert!((pi[0] - B / (A + B)).abs() < 1e-12);
+    assert!((pi[1] - A / (A + B)).abs() < 1e-12);
+}
+
+// from state 0 the chain leaves with probability a per step: geometric, mean 1 / a.
+#[test]
+fn markov_two_state_expected_hitting_times() {
+    let times = two_state(0, 1).expected_hitting_times(1);
+
+    assert_eq!(times[1], 0.0);
+    assert!((times[0] - 1.0 / A).abs() < 1e-12);
+}
+
+// state 2 is absorbing: from 0 the target 1 may never be reached, from 2 it never is.
+#[test]
+fn markov_hitting_times_infinite_unless_certain_to_reach() {
+    let transitions = vec![vec![0.0, 0.5, 0.5], vec![0.5, 0.5, 0.0], vec![0.0, 0.0, 1.0]];
+    let chain = MarkovChain::<f64>::new(transitions, vec![0.0, 1.0, 2.0], 0, 0.0, 1.0, 0);
+
+    let to_one = chain.expected_hitting_times(1);
+    assert!(to_one[0].is_infinite());
+    assert!(to_one[2].is_infinite());
+
+    // everything ends up in 2: h0 = 1 + h1 / 2, h1 = 1 + h0 / 2 + h1 / 2 -> h0 = 4, h1 = 6.
+    let to_two = chain.expected_hitting_times(2);
+    assert!((to_two[0] - 4.0).abs() < 1e-12);
+    assert!((to_two[1] - 6.0).abs() < 1e-12);
+}
+
+// rows that sum to 1 only up to f32 rounding are accepted: ten times 0.1f32 misses 1 by an ulp.
+#[test]
+fn markov_accepts_f32_rows() {
+    let n = 10;
+    let transitions = vec![vec![0.1f32; n]; n];
+    let chain = MarkovChain::new(transitions, vec![0.0f32; n], 0, 0.0, 1.0, 0);
+
+    let pi = chain.stationary_distribution().unwrap();
+    assert!(pi.iter().all(|&p| (p - 0.1).abs() < 1e-5));
+}
+
+// states equal in value stay apart: the state is recorded, not recovered from y.
+#[test]
+fn markov_push_back_keeps_current_state() {
+    let transitions = vec![vec![0.0, 1.0], vec![1.0, 0.0]];
+    let mut chain = MarkovChain::with_seed(transitions, vec![5.0, 5.0], 0, 0.0, 1.0, 1, 1);
+
+    chain.generate();
+    assert_eq!(chain.state(), 1);
+
+    chain.push_back(Point { x: 2.0, y: 7.0 });
+    assert_eq!(chain.state(), 1);
+    assert_eq!(chain.states().map(|(_, state)| state).collect::<Vec<_>>(), vec![1, 1]);
+}
+
+// the state history is bounded by the storage, like the path.
+#[test]
+fn markov_states_follow_storage() {
+    let mut chain = two_state(1000, 3).with_storage(RingBuffer::new(10));
+    chain.generate();
+
+    assert_eq!(chain.states().count(), 10);
+    assert!(chain.states().zip(chain.path().iter()).all(|((x, state), point)| x == point.x && state as f64 == point.y));
+    assert_eq!(chain.hitting_time(chain.state()).map(|x| x > 990.0), Some(true));
+}
+
+const ARRIVAL: f64 = 2.0;
+const SERVICE: f64 = 3.0;
+
+// pi = (mu, lambda) / (lambda + mu) for a single birth-death step.
+#[test]
+fn ctmc_two_state_stationary_distribution() {
+    let chain = ContinuousTimeMarkovChain::birth_death(&[ARRIVAL], &[SERVICE], 0, 0.0, 0.0, seeded_rng(1));
+    let pi = chain.stationary_distribution().unwrap();
+
+    assert!((pi[0] - SERVICE / (ARRIVAL + SERVICE)).abs() < 1e-12);
+    assert!((pi[1] - ARRIVAL / (ARRIVAL + SERVICE)).abs() < 1e-12);
+}
+
+// large rates in f32: rows sum to 0 only up to rounding relative to the rates.
+// every column sums to 0 as well -> uniform stationary distribution.
+#[test]
+fn ctmc_accepts_f32_generator() {
+    let generator = vec![
+        vec![-300.3f32, 100.1, 200.2],
+        vec![200.2, -300.3, 100.1],
+        vec![100.1, 200.2, -300.3],
+    ];
+    let chain = ContinuousTimeMarkovChain::new(generator, vec![0.0f32, 1.0, 2.0], 0, 0.0, 1.0);
+
+    let pi = chain.stationary_distribution().unwrap();
+    assert!(pi.iter().all(|&p| (p - 1.0 / 3.0).abs() < 1e-5));
+}
+
+// m/m/1/k: truncated geometric, pi_n proportional to (lambda / mu)^n for n = 0..=k.
+#[test]
+fn ctmc_mm1k_stationary_distribution() {
+    let capacity = 4;
+    let chain = ContinuousTimeMarkovChain::mm1k_queue(ARRIVAL, SERVICE, capacity, 0, 0.0, 0.0, seeded_rng(1));
+    let pi = chain.stationary_distribution().unwrap();
+
+    let rho = ARRIVAL / SERVICE;
+    let norm: f64 = (0..=capacity).map(|n| rho.powi(n as i32)).sum();
+    assert_eq!(pi.len(), capacity + 1);
+    assert!(pi.iter().enumerate().all(|(n, &p)| (p - rho.powi(n as i32) / norm).abs() < 1e-12));
+}
+
+#[test]
+fn ctmc_push_back_keeps_current_state() {
+    let mut chain = ContinuousTimeMarkovChain::birth_death(&[ARRIVAL], &[SERVICE], 1, 0.0, 10.0, seeded_rng(1));
+
+    chain.push_back(Point { x: 0.5, y: 0.0 });
+    assert_eq!(chain.state(), 1);
+    assert_eq!(chain.state_at(0.25), Some(1));
+}
+
+// the jump history is bounded by the storage. before the first kept jump the state is unknown.
+#[test]
+fn ctmc_jumps_follow_storage() {
+    let mut chain = ContinuousTimeMarkovChain::birth_death(&[ARRIVAL], &[SERVICE], 0, 0.0, 100.0, seeded_rng(2))
+        .with_storage(RingBuffer::new(5));
+    chain.generate();
+
+    let (first, _) = chain.jumps().next().unwrap();
+    assert_eq!(chain.jumps().count(), 5);
+    assert_eq!(chain.state_at(first / 2.0), None);
+    assert_eq!(chain.state_at(100.0), Some(chain.state()));
+
+    let occupied: f64 = chain.occupation_times().iter().sum();
+    assert!((occupied - (100.0 - first)).abs() < 1e-9);
+}
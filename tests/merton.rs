@@ -0,0 +1,42 @@
+mod common;
+
+use common::mean_variance;
+use process_lib::base::*;
+use process_lib::ensemble::simulate_paths;
+use process_lib::merton::*;
+
+const PARAMS: MertonParams = MertonParams {
+    drift: 0.05,
+    volatility: 0.2,
+    intensity: 2.0,
+    jump_mean: -0.1,
+    jump_volatility: 0.15,
+};
+const INITIAL: f64 = 100.0;
+const PATHS: usize = 20000;
+
+// the compensator keeps the expected growth at mu, jumps included: E[S(T)] = S(0) * e^(mu * T).
+#[test]
+fn merton_expected_terminal_value() {
+    let (step, loops) = (0.05, 20);
+    let t = step * loops as f64;
+
+    let ensemble = simulate_paths(PATHS, 7, |rng| {
+        MertonJumpDiffusion::with_rng(Point { x: 0.0, y: INITIAL }, PARAMS, step, loops, rng)
+    });
+    let (mean, _) = mean_variance(&ensemble.terminal());
+    let expected = INITIAL * (PARAMS.drift * t).exp();
+
+    assert!((mean / expected - 1.0).abs() < 0.01, "mean {} != {}", mean, expected);
+}
+
+// applied jumps are bounded by the storage like the path.
+#[test]
+fn merton_jumps_follow_storage() {
+    let mut merton = MertonJumpDiffusion::with_seed(Point { x: 0.0, y: INITIAL }, PARAMS, 0.1, 1000, 3)
+        .with_storage(RingBuffer::new(4));
+    merton.generate();
+
+    assert_eq!(merton.jumps().len(), 4);
+    assert!(merton.jump_times().all(|x| x > 90.0));
+}
@@ -0,0 +1,45 @@
+mod common;
+
+use common::mean_variance;
+use process_lib::base::*;
+use process_lib::ensemble::simulate_paths;
+use process_lib::ornstein::OrnsteinUhlenbeck;
+
+const THETA: f64 = 2.0;
+const MU: f64 = 1.5;
+const SIGMA: f64 = 0.5;
+const PATHS: usize = 4000;
+
+// terminal values after theta * t = 20 are drawn from the stationary distribution N( mu, sigma^2 / (2 * theta) ).
+fn assert_stationary(step: f64, loops: u32) {
+    let initial = Point { x: 0.0, y: -3.0 };
+
+    let ensemble = simulate_paths(PATHS, 3, |rng| {
+        OrnsteinUhlenbeck::with_rng(initial, THETA, MU, SIGMA, step, loops, rng)
+    });
+    let (mean, variance) = mean_variance(&ensemble.terminal());
+
+    let expected_variance = SIGMA * SIGMA / (2.0 * THETA);
+
+    assert!((mean - MU).abs() < 0.02, "mean {} != {}", mean, MU);
+    assert!((variance - expected_variance).abs() < 0.006, "variance {} != {}", variance, expected_variance);
+}
+
+#[test]
+fn ou_small_steps_reach_stationary_distribution() {
+    assert_stationary(0.01, 1000);
+}
+
+// exact discretisation: a single large step per unit of theta is still distributed correctly.
+#[test]
+fn ou_large_steps_reach_stationary_distribution() {
+    assert_stationary(0.5, 20);
+}
+
+#[test]
+fn ou_stationary_moments() {
+    let ou = OrnsteinUhlenbeck::new(Point { x: 0.0, y: 0.0 }, THETA, MU, SIGMA, 0.1, 10);
+
+    assert_eq!(ou.stationary_mean(), MU);
+    assert_eq!(ou.stationary_variance(), SIGMA * SIGMA / (2.0 * THETA));
+}
@@ -0,0 +1,113 @@
+use process_lib::base::*;
+use process_lib::ensemble::simulate_paths;
+use process_lib::poisson::*;
+use rand::{Rng, RngCore};
+
+const PATHS: usize = 4000;
+
+// number of arrivals of every path, averaged.
+fn mean_count(ensemble: &process_lib::ensemble::Ensemble) -> f64 {
+    ensemble.paths().map(|path| path.len() as f64).sum::<f64>() / ensemble.len() as f64
+}
+
+// ramp from 0 to 4 over [0, 10]: E[N(10)] = integral of lambda = 20.
+#[test]
+fn non_homogeneous_piecewise_mean_count() {
+    let ensemble = simulate_paths(PATHS, 3, |rng| {
+        NonHomogeneousPoisson::with_rng(Point { x: 0.0, y: 0.0 }, 10.0, PiecewiseIntensity::new(vec![(0.0, 0.0), (10.0, 4.0)]), rng)
+    });
+    let mean = mean_count(&ensemble);
+
+    assert!((mean - 20.0).abs() < 0.3, "mean count {} != 20", mean);
+}
+
+#[test]
+fn non_homogeneous_closure_mean_count() {
+    let ensemble = simulate_paths(PATHS, 5, |rng| {
+        NonHomogeneousPoisson::with_rng(Point { x: 0.0, y: 0.0 }, 10.0, IntensityFn::new(|t: f64| 0.4 * t, 4.0), rng)
+    });
+    let mean = mean_count(&ensemble);
+
+    assert!((mean - 20.0).abs() < 0.3, "mean count {} != 20", mean);
+}
+
+#[test]
+#[should_panic(expected = "upper bound must be finite and not negative")]
+fn intensity_fn_rejects_infinite_bound() {
+    IntensityFn::new(|_: f64| 1.0, f64::INFINITY);
+}
+
+#[test]
+#[should_panic(expected = "upper bound must be finite and not negative")]
+fn intensity_fn_rejects_nan_bound() {
+    IntensityFn::new(|_: f64| 1.0, f64::NAN);
+}
+
+// the clock restarts with the storage: arrivals again cover the whole distance.
+#[test]
+fn poisson_with_storage_restarts_clock() {
+    let mut poisson = Poisson::with_seed(Point { x: 0.0, y: 0.0 }, 1.0, 100.0, 1);
+    while poisson.path().tail().is_none_or(|tail| tail.x < 50.0) {
+        poisson.generate_single();
+    }
+
+    let mut ring = poisson.with_storage(Path::new());
+    ring.generate();
+
+    assert!(ring.path().iter().next().is_some_and(|first| first.x < 50.0));
+    assert!(ring.path().len() > 60);
+}
+
+// final sum of every path, the initial value if nothing arrived.
+fn mean_terminal(ensemble: &process_lib::ensemble::Ensemble, initial: f64) -> f64 {
+    ensemble.paths().map(|path| path.last().map_or(initial, |point| point.y)).sum::<f64>() / ensemble.len() as f64
+}
+
+// E[S(T)] = S(0) + T / average_dist * E[J]: 20 arrivals of mean size 1 on average.
+#[test]
+fn compound_poisson_mean_with_exponential_jumps() {
+    let ensemble = simulate_paths(PATHS, 7, |rng| {
+        CompoundPoisson::with_rng(Point { x: 0.0, y: 5.0 }, 0.5, 10.0, rand_distr::Exp::new(1.0).unwrap(), rng)
+    });
+    let mean = mean_terminal(&ensemble, 5.0);
+
+    assert!((mean - 25.0).abs() < 0.4, "mean {} != 25", mean);
+}
+
+// user closure: 4 with probability 1/4, else 0 -> E[J] = 1.
+#[test]
+fn compound_poisson_mean_with_closure_jumps() {
+    let jumps = FromFn(|rng: &mut dyn RngCore| if rng.gen_bool(0.25) { 4.0 } else { 0.0 });
+    let ensemble = simulate_paths(PATHS, 9, |rng| CompoundPoisson::with_rng(Point { x: 0.0, y: 5.0 }, 0.5, 10.0, jumps, rng));
+    let mean = mean_terminal(&ensemble, 5.0);
+
+    assert!((mean - 25.0).abs() < 0.6, "mean {} != 25", mean);
+}
+
+// E[N] = total_dist / average_dist.
+#[test]
+fn poisson_mean_count() {
+    let ensemble = simulate_paths(PATHS, 13, |rng| Poisson::with_rng(Point { x: 0.0, y: 0.0 }, 0.25, 10.0, rng));
+    let mean = mean_count(&ensemble);
+
+    assert!((mean - 40.0).abs() < 0.4, "mean count {} != 40", mean);
+}
+
+// y counts every arrival, x increases and stays within the generated distance.
+#[test]
+fn poisson_counts_up_inside_total_dist() {
+    let initial = Point { x: 3.0, y: 7.0 };
+    let mut poisson = Poisson::with_seed(initial, 0.5, 20.0, 2);
+    poisson.generate();
+
+    let points: Vec<Point> = poisson.path().iter().copied().collect();
+    assert!(!points.is_empty());
+
+    let mut previous = initial;
+    for point in &points {
+        assert_eq!(point.y, previous.y + 1.0);
+        assert!(point.x > previous.x);
+        previous = *point;
+    }
+    assert!(previous.x <= initial.x + 20.0);
+}
@@ -0,0 +1,78 @@
+use process_lib::base::*;
+use process_lib::rwalk::*;
+
+#[test]
+fn pop_back_keeps_walk_absorbed_at_barrier() {
+    // always moves up: absorbed at 3 after three steps, then stays there.
+    let mut walk = RandomWalk1D::with_seed(Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 1.0 }, 6, 1)
+        .with_probabilities(1.0, 0.0)
+        .with_upper_barrier(Barrier::Absorbing(3.0));
+    walk.generate();
+
+    assert!(walk.is_absorbed());
+    walk.pop_back();
+    assert!(walk.is_absorbed(), "tail is still on the absorbing barrier");
+
+    // back below the barrier: free again.
+    while walk.path().tail().is_some_and(|tail| tail.y >= 3.0) {
+        walk.pop_back();
+    }
+    assert!(!walk.is_absorbed());
+}
+
+// a step reflected at the upper barrier overshoots the lower, absorbing one: absorbed there.
+#[test]
+fn reflection_onto_absorbing_barrier_absorbs() {
+    let mut walk = RandomWalk1D::with_seed(Point { x: 0.0, y: 1.0 }, Point { x: 1.0, y: 25.0 }, 3, 1)
+        .with_probabilities(1.0, 0.0)
+        .with_lower_barrier(Barrier::Absorbing(0.0))
+        .with_upper_barrier(Barrier::Reflecting(10.0));
+    walk.generate_single();
+
+    assert_eq!(walk.path().tail().map(|tail| tail.y), Some(0.0));
+    assert!(walk.is_absorbed());
+
+    walk.generate();
+    assert!(walk.path().iter().all(|point| point.y == 0.0));
+}
+
+// x in [0, 4], y in [-1, 1]
+fn bounds(mode: BoundaryMode) -> Bounds<f64, 2> {
+    Bounds::new([0.0, -1.0], [4.0, 1.0], mode)
+}
+
+#[test]
+fn bounds_wrap_re_enters_from_the_opposite_side() {
+    let wrap = bounds(BoundaryMode::Wrap);
+
+    assert_eq!(wrap.confine([1.5, 0.5]), [1.5, 0.5]);
+    assert_eq!(wrap.confine([5.0, 3.5]), [1.0, -0.5]);
+    assert_eq!(wrap.confine([-0.5, -1.5]), [3.5, 0.5]);
+    assert_eq!(wrap.confine([4.0, -1.0]), [0.0, -1.0]);
+    // several widths away
+    assert_eq!(wrap.confine([13.0, -6.5]), [1.0, -0.5]);
+    // just below the lower end: -1e-20 + 4 rounds to 4, which must wrap to 0, not stay at the upper end.
+    assert_eq!(wrap.confine([-1e-20, 0.0]), [0.0, 0.0]);
+}
+
+#[test]
+fn bounds_reflect_mirrors_back_inside() {
+    let reflect = bounds(BoundaryMode::Reflect);
+
+    assert_eq!(reflect.confine([1.5, 0.5]), [1.5, 0.5]);
+    assert_eq!(reflect.confine([5.0, 1.5]), [3.0, 0.5]);
+    assert_eq!(reflect.confine([-0.5, -1.5]), [0.5, -0.5]);
+    assert_eq!(reflect.confine([4.0, 1.0]), [4.0, 1.0]);
+    // beyond the opposite side: reflected twice
+    assert_eq!(reflect.confine([9.0, 3.5]), [1.0, -0.5]);
+}
+
+#[test]
+fn bounded_walk_stays_inside() {
+    for mode in [BoundaryMode::Wrap, BoundaryMode::Reflect] {
+        let mut walk = RandomWalk2D::with_seed(Point { x: 2.0, y: 0.0 }, 0.75, 1000, Neighbourhood::ContinuousAngle, 4).with_bounds(bounds(mode));
+        walk.generate();
+
+        assert!(walk.path().iter().all(|p| (0.0..=4.0).contains(&p.x) && (-1.0..=1.0).contains(&p.y)));
+    }
+}
@@ -0,0 +1,57 @@
+use process_lib::base::*;
+use process_lib::sde::*;
+
+const DRIFT: f64 = 0.1;
+const VOLATILITY: f64 = 0.5;
+const INITIAL: f64 = 1.0;
+const HORIZON: f64 = 1.0;
+const PATHS: u64 = 200;
+
+// mean absolute error at the horizon against exact gbm driven by the same brownian path.
+// the process draws one standard normal per step, so the same seed reproduces its increments.
+fn strong_error(scheme: SdeScheme, loops: u32) -> f64 {
+    let step = HORIZON / loops as f64;
+    let mut total = 0.0;
+
+    for seed in 0..PATHS {
+        let mut sde = Sde::with_seed(Point { x: 0.0, y: INITIAL }, |_, x| DRIFT * x, |_, x| VOLATILITY * x, step, loops, seed)
+            .with_diffusion_derivative(|_, _| VOLATILITY)
+            .with_scheme(scheme);
+        sde.generate();
+
+        let mut rng = seeded_rng(seed);
+        let w: f64 = (0..loops).map(|_| step.sqrt() * standard_normal::<f64, _>(&mut rng)).sum();
+        let exact = INITIAL * ((DRIFT - 0.5 * VOLATILITY * VOLATILITY) * HORIZON + VOLATILITY * w).exp();
+
+        total += (sde.path().tail().unwrap().y - exact).abs();
+    }
+    total / PATHS as f64
+}
+
+// strong order 1: a quarter of the step, a quarter of the error. euler-maruyama only halves it.
+#[test]
+fn sde_milstein_converges_to_exact_gbm() {
+    let (coarse, fine) = (strong_error(SdeScheme::Milstein, 16), strong_error(SdeScheme::Milstein, 64));
+    let (euler_coarse, euler_fine) = (strong_error(SdeScheme::EulerMaruyama, 16), strong_error(SdeScheme::EulerMaruyama, 64));
+
+    assert!(fine < coarse / 3.0, "milstein error {} -> {}", coarse, fine);
+    assert!(euler_fine > euler_coarse / 3.0, "euler error {} -> {}", euler_coarse, euler_fine);
+    assert!(fine < euler_fine);
+}
+
+// step length follows the local error: spacing varies and the last point lands on total_dist.
+#[test]
+fn adaptive_sde_steps_vary_and_end_at_total_dist() {
+    let start = 0.3;
+    let total_dist = 2.0;
+    let mut sde = AdaptiveSde::with_seed(Point { x: start, y: INITIAL }, |_, x| DRIFT * x, |_, x| VOLATILITY * x, 0.25, 1e-3, total_dist, 5);
+    sde.generate();
+
+    let xs: Vec<f64> = sde.path().iter().map(|point| point.x).collect();
+    let gaps: Vec<f64> = xs.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    let (shortest, longest) = gaps.iter().fold((f64::INFINITY, 0.0f64), |(lo, hi), &gap| (lo.min(gap), hi.max(gap)));
+
+    assert!(gaps.iter().all(|&gap| gap > 0.0));
+    assert!(longest > 2.0 * shortest, "spacing is uniform: {} .. {}", shortest, longest);
+    assert_eq!(xs.last().copied(), Some(start + total_dist));
+}